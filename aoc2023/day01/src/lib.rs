@@ -10,26 +10,32 @@ impl Solution<i64, i64> for Day {
     fn meta() -> Meta<i64, i64> {
         Meta::<i64, i64> {
             input: include_str!("input.txt").to_string(),
-            sample_a: include_str!("input_sample_a.txt").to_string(),
-            sample_b: include_str!("input_sample_b.txt").to_string(),
-            answer_a: 142,
-            answer_b: 281,
+            samples_a: vec![Sample::new(
+                "sample a",
+                include_str!("input_sample_a.txt"),
+                142,
+            )],
+            samples_b: vec![Sample::new(
+                "sample b",
+                include_str!("input_sample_b.txt"),
+                281,
+            )],
         }
     }
 
-    fn new(raw: Vec<Rc<str>>) -> Self {
-        Self {
+    fn new(raw: Vec<Rc<str>>) -> ParseResult<Self> {
+        Ok(Self {
             raw: raw.clone(),
             lines: raw.iter().map(|line| line.chars().collect()).collect(),
-        }
+        })
     }
 
     fn part_a(&self) -> Option<i64> {
         let mut sum = 0;
 
         for line in &self.lines {
-            let a = *line.iter().find(|c| c.is_numeric()).unwrap() as u8;
-            let b = *line.iter().rev().find(|c| c.is_numeric()).unwrap() as u8;
+            let a = *line.iter().find(|c| c.is_ascii_digit())? as u8;
+            let b = *line.iter().rev().find(|c| c.is_ascii_digit())? as u8;
             sum += ((a - b'0') * 10 + (b - b'0')) as i64;
         }
 
@@ -85,8 +91,8 @@ impl Solution<i64, i64> for Day {
                 }
             }
 
-            let a = nums.first().unwrap();
-            let b = nums.last().unwrap();
+            let a = nums.first()?;
+            let b = nums.last()?;
             sum += a * 10 + b;
         }
 
@@ -17,20 +17,22 @@ impl Solution<usize, usize> for Day {
     fn meta() -> Meta<usize, usize> {
         Meta::<usize, usize> {
             input: include_str!("input.txt").to_string(),
-            sample_a: include_str!("input_sample.txt").to_string(),
-            sample_b: include_str!("input_sample.txt").to_string(),
-            answer_a: 8,
-            answer_b: 2286,
+            samples_a: vec![Sample::new("sample", include_str!("input_sample.txt"), 8)],
+            samples_b: vec![Sample::new(
+                "sample",
+                include_str!("input_sample.txt"),
+                2286,
+            )],
         }
     }
 
-    fn new(raw: Vec<Rc<str>>) -> Self {
+    fn new(raw: Vec<Rc<str>>) -> ParseResult<Self> {
         let mut games = Vec::new();
 
-        for line in raw.iter() {
+        for (index, line) in raw.iter().enumerate() {
             let mut game = Vec::new();
-            let sets: Vec<&str> = line.split_once(": ").unwrap().1.split("; ").collect();
-            for set in sets {
+            let (_, sets) = line.split_once(": ").context(index, "`: `")?;
+            for set in sets.split("; ") {
                 let mut cubes = Set {
                     red: 0,
                     green: 0,
@@ -38,8 +40,8 @@ impl Solution<usize, usize> for Day {
                 };
 
                 for pull in set.split(", ") {
-                    let (count, color) = pull.split_once(' ').unwrap();
-                    let count: usize = count.parse().unwrap();
+                    let (count, color) = pull.split_once(' ').context(index, "a cube color")?;
+                    let count: usize = parse_field(index, line, count)?;
                     match color {
                         "red" => cubes.red = count,
                         "green" => cubes.green = count,
@@ -54,10 +56,10 @@ impl Solution<usize, usize> for Day {
             games.push(game);
         }
 
-        Self {
+        Ok(Self {
             raw: raw.clone(),
             games,
-        }
+        })
     }
 
     fn part_a(&self) -> Option<usize> {
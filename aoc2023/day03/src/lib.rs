@@ -11,14 +11,20 @@ impl Solution<i64, i64> for Day {
     fn meta() -> Meta<i64, i64> {
         Meta::<i64, i64> {
             input: include_str!("input.txt").to_string(),
-            sample_a: include_str!("input_sample.txt").to_string(),
-            sample_b: include_str!("input_sample.txt").to_string(),
-            answer_a: 4361,
-            answer_b: 467835,
+            samples_a: vec![Sample::new(
+                "sample",
+                include_str!("input_sample.txt"),
+                4361,
+            )],
+            samples_b: vec![Sample::new(
+                "sample",
+                include_str!("input_sample.txt"),
+                467835,
+            )],
         }
     }
 
-    fn new(raw: Vec<Rc<str>>) -> Self {
+    fn new(raw: Vec<Rc<str>>) -> ParseResult<Self> {
         let size = Vector::new_usize(raw[0].len(), raw.len());
         let mut numbers: VectorMap<i64> = VectorMap::new(size);
         let mut symbols: VectorMap<Vec<i64>> = VectorMap::new(size);
@@ -65,10 +71,10 @@ impl Solution<i64, i64> for Day {
             }
         }
 
-        Self {
+        Ok(Self {
             raw: raw.clone(),
             parts: symbols,
-        }
+        })
     }
 
     fn part_a(&self) -> Option<i64> {
@@ -11,33 +11,31 @@ impl Solution<u32, u32> for Day {
     fn meta() -> Meta<u32, u32> {
         Meta::<u32, u32> {
             input: include_str!("input.txt").to_string(),
-            sample_a: include_str!("input_sample.txt").to_string(),
-            sample_b: include_str!("input_sample.txt").to_string(),
-            answer_a: 13,
-            answer_b: 30,
+            samples_a: vec![Sample::new("sample", include_str!("input_sample.txt"), 13)],
+            samples_b: vec![Sample::new("sample", include_str!("input_sample.txt"), 30)],
         }
     }
 
-    fn new(raw: Vec<Rc<str>>) -> Self {
+    fn new(raw: Vec<Rc<str>>) -> ParseResult<Self> {
         let mut cards = Vec::new();
-        for line in raw.iter() {
-            let parts = line.split_once(": ").unwrap().1;
-            let (winning, numbers) = parts.split_once(" | ").unwrap();
+        for (index, line) in raw.iter().enumerate() {
+            let (_, parts) = line.split_once(": ").context(index, "`: `")?;
+            let (winning, numbers) = parts.split_once(" | ").context(index, "` | `")?;
             let winning: HashSet<u8> = winning
                 .split_whitespace()
-                .map(|n| n.parse().unwrap())
-                .collect();
+                .map(|n| parse_field(index, line, n))
+                .collect::<ParseResult<_>>()?;
             let numbers: HashSet<u8> = numbers
                 .split_whitespace()
-                .map(|n| n.parse().unwrap())
-                .collect();
+                .map(|n| parse_field(index, line, n))
+                .collect::<ParseResult<_>>()?;
             cards.push(winning.intersection(&numbers).count() as u32);
         }
 
-        Self {
+        Ok(Self {
             raw: raw.clone(),
             cards,
-        }
+        })
     }
 
     fn part_a(&self) -> Option<u32> {
@@ -1,7 +1,8 @@
-#![feature(slice_split_once)]
-
-use std::{ops::Range, str::FromStr};
-use utils::prelude::*;
+use std::ops::Range;
+use utils::{
+    parse::{Field, FromField, sections},
+    prelude::*,
+};
 
 pub struct Day {
     #[allow(dead_code)]
@@ -91,37 +92,39 @@ impl Solution<i64, i64> for Day {
     fn meta() -> Meta<i64, i64> {
         Meta::<i64, i64> {
             input: include_str!("input.txt").to_string(),
-            sample_a: include_str!("input_sample.txt").to_string(),
-            sample_b: include_str!("input_sample.txt").to_string(),
-            answer_a: 35,
-            answer_b: 46,
+            samples_a: vec![Sample::new("sample", include_str!("input_sample.txt"), 35)],
+            samples_b: vec![Sample::new("sample", include_str!("input_sample.txt"), 46)],
         }
     }
 
-    fn new(raw: Vec<Rc<str>>) -> Self {
-        let (seeds, map_sections) = raw.split_once(|line| line.is_empty()).unwrap();
-        let map_sections: Vec<_> = map_sections.split(|line| line.is_empty()).collect();
-        let seeds = seeds[0].split_once(": ").unwrap().1;
-        let seeds = seeds
-            .split_whitespace()
-            .map(|s| s.parse().unwrap())
-            .collect();
+    fn new(raw: Vec<Rc<str>>) -> ParseResult<Self> {
+        let sections = sections(&raw);
+        let Some((seeds, map_sections)) = sections.split_first() else {
+            return Err(ParseError::new("missing seeds"));
+        };
+        if map_sections.is_empty() {
+            return Err(ParseError::on_line(
+                raw.len(),
+                "missing maps after the seeds",
+            ));
+        }
+        let (_, seeds) = seeds.header()?.key_values()?;
 
         let mut maps = Vec::new();
-        for map_section in map_sections {
-            let mut map: Vec<Mapping> = Vec::new();
-            for line in map_section.iter().skip(1) {
-                map.push(line.parse().unwrap());
-            }
+        for section in map_sections {
+            let mut map = section
+                .body()
+                .map(|line| line.get())
+                .collect::<ParseResult<Vec<Mapping>>>()?;
             map.sort_by_key(|m| m.range.start);
             maps.push(map);
         }
 
-        Self {
+        Ok(Self {
             raw: raw.clone(),
             seeds,
             maps,
-        }
+        })
     }
 
     fn part_a(&self) -> Option<i64> {
@@ -141,15 +144,13 @@ impl Solution<i64, i64> for Day {
     }
 }
 
-impl FromStr for Mapping {
-    type Err = ();
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let parts: Vec<_> = s.split_whitespace().collect();
-        let parts: Vec<i64> = parts.iter().map(|p| p.parse().unwrap()).collect();
+/// Written `destination source length`.
+impl FromField for Mapping {
+    fn from_field(field: Field) -> ParseResult<Self> {
+        let [destination, source, length] = field.array(" ")?;
         Ok(Mapping {
-            range: parts[1]..parts[1] + parts[2],
-            offset: parts[0] - parts[1],
+            range: source..source + length,
+            offset: destination - source,
         })
     }
 }
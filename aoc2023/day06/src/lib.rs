@@ -35,25 +35,30 @@ impl Solution<usize, usize> for Day {
     fn meta() -> Meta<usize, usize> {
         Meta::<usize, usize> {
             input: include_str!("input.txt").to_string(),
-            sample_a: include_str!("input_sample.txt").to_string(),
-            sample_b: include_str!("input_sample.txt").to_string(),
-            answer_a: 288,
-            answer_b: 71503,
+            samples_a: vec![Sample::new("sample", include_str!("input_sample.txt"), 288)],
+            samples_b: vec![Sample::new(
+                "sample",
+                include_str!("input_sample.txt"),
+                71503,
+            )],
         }
     }
 
-    fn new(raw: Vec<Rc<str>>) -> Self {
-        let times: Vec<i64> = raw[0]
+    fn new(raw: Vec<Rc<str>>) -> ParseResult<Self> {
+        let [times, distances] = &raw[..] else {
+            return Err(ParseError::new("expected times and distances"));
+        };
+        let times: Vec<i64> = times
             .split_whitespace()
             .skip(1)
-            .map(|n| n.parse().unwrap())
-            .collect();
-        let distances: Vec<i64> = raw[1]
+            .map(|n| parse_field(0, times, n))
+            .collect::<ParseResult<_>>()?;
+        let distances: Vec<i64> = distances
             .split_whitespace()
             .skip(1)
-            .map(|n| n.parse().unwrap())
-            .collect();
-        Self {
+            .map(|n| parse_field(1, distances, n))
+            .collect::<ParseResult<_>>()?;
+        Ok(Self {
             raw: raw.clone(),
             races: times
                 .into_iter()
@@ -67,16 +72,16 @@ impl Solution<usize, usize> for Day {
                     .collect::<Vec<_>>()
                     .join("")
                     .parse()
-                    .unwrap(),
+                    .context(0, "time")?,
                 distance: raw[1]
                     .split_whitespace()
                     .skip(1)
                     .collect::<Vec<_>>()
                     .join("")
                     .parse()
-                    .unwrap(),
+                    .context(1, "distance")?,
             },
-        }
+        })
     }
 
     fn part_a(&self) -> Option<usize> {
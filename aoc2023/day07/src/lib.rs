@@ -1,7 +1,6 @@
 use std::{
     cmp::Ordering,
     collections::{HashMap, HashSet},
-    str::FromStr,
 };
 use utils::prelude::*;
 
@@ -105,18 +104,28 @@ impl Solution<i64, i64> for Day {
     fn meta() -> Meta<i64, i64> {
         Meta::<i64, i64> {
             input: include_str!("input.txt").to_string(),
-            sample_a: include_str!("input_sample.txt").to_string(),
-            sample_b: include_str!("input_sample.txt").to_string(),
-            answer_a: 6440,
-            answer_b: 5905,
+            samples_a: vec![Sample::new(
+                "sample",
+                include_str!("input_sample.txt"),
+                6440,
+            )],
+            samples_b: vec![Sample::new(
+                "sample",
+                include_str!("input_sample.txt"),
+                5905,
+            )],
         }
     }
 
-    fn new(raw: Vec<Rc<str>>) -> Self {
-        Self {
+    fn new(raw: Vec<Rc<str>>) -> ParseResult<Self> {
+        Ok(Self {
             raw: raw.clone(),
-            hands: raw.iter().map(|h| h.parse().unwrap()).collect(),
-        }
+            hands: raw
+                .iter()
+                .enumerate()
+                .map(|(index, line)| Hand::parse(index, line))
+                .collect::<ParseResult<_>>()?,
+        })
     }
 
     fn part_a(&self) -> Option<i64> {
@@ -152,12 +161,10 @@ impl Solution<i64, i64> for Day {
     }
 }
 
-impl FromStr for Hand {
-    type Err = ();
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (cards, bid) = s.split_once(' ').unwrap();
-        let bid: i64 = bid.parse().unwrap();
+impl Hand {
+    fn parse(index: usize, line: &str) -> ParseResult<Self> {
+        let (cards, bid) = line.split_once(' ').context(index, "a bid")?;
+        let bid: i64 = parse_field(index, line, bid)?;
         Ok(Hand {
             name: Name::new(cards),
             cards: cards.into(),
@@ -12,31 +12,58 @@ impl Solution<usize, usize> for Day {
     fn meta() -> Meta<usize, usize> {
         Meta::<usize, usize> {
             input: include_str!("input.txt").to_string(),
-            sample_a: include_str!("input_sample_a.txt").to_string(),
-            sample_b: include_str!("input_sample_b.txt").to_string(),
-            answer_a: 6,
-            answer_b: 6,
+            samples_a: vec![
+                Sample::new("RL", include_str!("input_sample_a2.txt"), 2),
+                Sample::new("LLR", include_str!("input_sample_a.txt"), 6),
+            ],
+            samples_b: vec![Sample::new("LR", include_str!("input_sample_b.txt"), 6)],
         }
     }
 
-    fn new(raw: Vec<Rc<str>>) -> Self {
+    fn new(raw: Vec<Rc<str>>) -> ParseResult<Self> {
         let mut map = HashMap::new();
-        for line in raw.iter().skip(2) {
-            let name = line[0..3].into();
-            let left = line[7..10].into();
-            let right = line[12..15].into();
-            map.insert(name, (left, right));
+        let mut links = vec![];
+        for (index, line) in raw.iter().enumerate().skip(2) {
+            let (name, next) = line.split_once(" = ").context(index, "` = `")?;
+            let (left, right) = next
+                .strip_prefix('(')
+                .and_then(|next| next.strip_suffix(')'))
+                .and_then(|next| next.split_once(", "))
+                .context(index, "`(left, right)`")?;
+            map.insert(name.into(), (left.into(), right.into()));
+            links.extend([(index, left), (index, right)]);
+        }
+        let unknown = links.into_iter().find(|(_, node)| !map.contains_key(*node));
+        if let Some((index, node)) = unknown {
+            return Err(ParseError::on_line(index, format!("unknown node `{node}`")));
+        }
+
+        let dirs = raw[0]
+            .chars()
+            .enumerate()
+            .map(|(column, c)| match c {
+                'L' => Ok(false),
+                'R' => Ok(true),
+                _ => Err(ParseError::at(
+                    0,
+                    column,
+                    format!("invalid direction `{c}`"),
+                )),
+            })
+            .collect::<ParseResult<Vec<_>>>()?;
+        if dirs.is_empty() {
+            return Err(ParseError::on_line(0, "missing directions"));
         }
 
-        Self {
+        Ok(Self {
             raw: raw.clone(),
-            dirs: raw[0].chars().map(|c| c == 'R').collect(),
+            dirs,
             map,
-        }
+        })
     }
 
     fn part_a(&self) -> Option<usize> {
-        Some(self.count("AAA", "ZZZ"))
+        self.count("AAA", "ZZZ")
     }
 
     fn part_b(&self) -> Option<usize> {
@@ -44,21 +71,22 @@ impl Solution<usize, usize> for Day {
             .map
             .keys()
             .filter(|p| p.ends_with('A'))
-            .map(|p| self.count(p, "Z") as u64)
-            .collect();
+            .map(|p| self.count(p, "Z").map(|count| count as u64))
+            .collect::<Option<_>>()?;
         let primes: HashSet<_> = loops.into_iter().flat_map(primes::factors_uniq).collect();
         Some(primes.iter().product::<u64>() as usize)
     }
 }
 
 impl Day {
-    fn count(&self, start: &str, end: &str) -> usize {
+    /// The steps from `start` to a node ending in `end`, or `None` if `start` doesn't exist.
+    fn count(&self, start: &str, end: &str) -> Option<usize> {
         let mut place = start;
         let mut step = 0;
 
         while !place.ends_with(end) {
             let dir = self.dirs[step % self.dirs.len()];
-            let next = self.map.get(place).unwrap();
+            let next = self.map.get(place)?;
             place = match dir {
                 true => &next.1,
                 false => &next.0,
@@ -66,7 +94,7 @@ impl Day {
             step += 1;
         }
 
-        step
+        Some(step)
     }
 }
 
@@ -1,128 +1,159 @@
-use std::collections::{HashMap, HashSet, VecDeque};
+use std::iter;
 use utils::prelude::*;
 
-use utils::{
-    direction::DIRS,
-    vector::{Vector, VectorSet},
-};
+use utils::vector::{Grid, Vector, VectorMap, VectorSet};
 
 pub struct Day {
     #[allow(dead_code)]
     raw: Vec<Rc<str>>,
-    grid: VectorSet,
+    grid: VectorMap<Plot>,
     size: Vector,
     start: Vector,
-    distances: HashMap<Vector, i64>,
+    params: Params,
 }
 
-impl Solution<i64, i64> for Day {
-    fn meta() -> Meta<i64, i64> {
-        Meta::<i64, i64> {
-            input: include_str!("input.txt").to_string(),
-            sample_a: include_str!("input_sample.txt").to_string(),
-            sample_b: include_str!("input_sample.txt").to_string(),
-            answer_a: 16,
-            answer_b: 16733999, // This answer is modified to fit the calculation
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Plot {
+    Garden,
+    Rock,
+}
+
+impl TryFrom<char> for Plot {
+    type Error = &'static str;
+
+    fn try_from(c: char) -> Result<Self, Self::Error> {
+        match c {
+            '.' | 'S' => Ok(Self::Garden),
+            '#' => Ok(Self::Rock),
+            _ => Err("not a garden plot or rock"),
         }
     }
+}
 
-    fn new(raw: Vec<Rc<str>>) -> Self {
-        let size = Vector::new_usize(raw[0].len(), raw.len());
-        let mut start: Option<Vector> = None;
-        let mut grid = VectorSet::new(size);
-        for (y, line) in raw.iter().enumerate() {
-            for (x, c) in line.chars().enumerate() {
-                let pos = Vector::new_usize(x, y);
-                if c == '#' {
-                    grid.insert(pos);
-                } else if c == 'S' {
-                    start = Some(pos);
-                }
-            }
+#[derive(Clone)]
+pub struct Params {
+    steps_a: i64,
+    steps_b: i64,
+}
+
+impl Default for Params {
+    fn default() -> Self {
+        Self {
+            steps_a: 64,
+            steps_b: 26501365,
         }
+    }
+}
 
-        let mut distances = HashMap::new();
-        let mut deque: VecDeque<(Vector, i64)> = VecDeque::from([(start.unwrap(), 0)]);
-        while let Some((pos, distance)) = deque.pop_front() {
-            if distances.contains_key(&pos) || !pos.contained_in(Vector::zero(), size) {
-                continue;
-            }
-            distances.insert(pos, distance);
-            for dir in DIRS {
-                let pos = pos + dir;
-                if !grid.contains(pos) {
-                    deque.push_back((pos, distance + 1));
-                }
-            }
+impl Solution<i64, i64, Params> for Day {
+    fn meta() -> Meta<i64, i64, Params> {
+        let sample = include_str!("input_sample.txt");
+        let steps_a = |steps_a| Params {
+            steps_a,
+            ..Default::default()
+        };
+        let steps_b = |steps_b| Params {
+            steps_b,
+            ..Default::default()
+        };
+        Meta::<i64, i64, Params> {
+            input: include_str!("input.txt").to_string(),
+            samples_a: vec![
+                Sample::new("6 steps", sample, 16).with_params(steps_a(6)),
+                Sample::new("10 steps", sample, 50).with_params(steps_a(10)),
+                Sample::new("50 steps", sample, 1594).with_params(steps_a(50)),
+                Sample::new("100 steps", sample, 6536).with_params(steps_a(100)),
+            ],
+            samples_b: vec![
+                Sample::new("500 steps", sample, 167004).with_params(steps_b(500)),
+                Sample::new("1000 steps", sample, 668697).with_params(steps_b(1000)),
+                Sample::new("5000 steps", sample, 16733044).with_params(steps_b(5000)),
+            ],
         }
+    }
 
-        Self {
+    fn new(raw: Vec<Rc<str>>) -> ParseResult<Self> {
+        Self::new_with(raw, &Params::default())
+    }
+
+    fn new_with(raw: Vec<Rc<str>>, params: &Params) -> ParseResult<Self> {
+        let (grid, markers) = VectorMap::from_grid(&raw, &['S'])?;
+        let size = grid.size();
+        let start = markers.one('S')?;
+
+        Ok(Self {
             raw: raw.clone(),
             grid,
             size,
-            start: start.unwrap(),
-            distances,
-        }
+            start,
+            params: params.clone(),
+        })
     }
 
     fn part_a(&self) -> Option<i64> {
-        let steps = if self.size.x < 20 { 6 } else { 64 };
-        let mut visited: HashSet<Vector> = HashSet::new();
-        let mut new: HashSet<Vector> = HashSet::from([self.start]);
-        for _ in 0..steps {
-            new = self.take_step(&visited, &new);
-            visited.extend(new.iter());
+        let steps = self.params.steps_a;
+        let mut reached = [0, 0];
+        for (distance, plots) in self.layers(steps).enumerate() {
+            reached[distance % 2] += plots;
         }
-        Some(visited.iter().filter(|v| (v.x + v.y) % 2 == 0).count() as i64)
+        Some(reached[(steps % 2) as usize])
     }
 
+    /// The map repeats every period, so once the plots reached grow by the same amount more each
+    /// period, the plots reached after `steps % period + k * period` steps are quadratic in `k`.
     fn part_b(&self) -> Option<i64> {
-        let steps = if self.size.x < 50 { 5000 } else { 26501365 };
-        let size = self.size.x / 2;
-
-        let odd_full = self
-            .distances
-            .values()
-            .filter(|&&dist| dist % 2 == 1)
-            .count() as i64;
-        let even_full = self
-            .distances
-            .values()
-            .filter(|&&dist| dist % 2 == 0)
-            .count() as i64;
-
-        let odd_corners = self
-            .distances
-            .values()
-            .filter(|&&dist| dist % 2 == 1 && dist > size)
-            .count() as i64;
-        let even_corners = self
-            .distances
-            .values()
-            .filter(|&&dist| dist % 2 == 0 && dist > size)
-            .count() as i64;
-
-        let n = (steps - size) / self.size.x;
-        let odd_full = (n + 1) * (n + 1) * odd_full;
-        let even_full = (n * n) * even_full;
-        let odd_corners = (n + 1) * odd_corners;
-        let even_corners = n * even_corners;
-        Some(odd_full + even_full - odd_corners + even_corners)
+        let steps = self.params.steps_b;
+        let period = self.size.x;
+        let radius = steps.min(steps % period + SETTLE * period);
+        let mut reached = [0, 0];
+        let mut periods = vec![];
+        for (distance, plots) in (0..).zip(self.layers(radius)) {
+            reached[(distance % 2) as usize] += plots;
+            let total = reached[(distance % 2) as usize];
+            if distance == steps {
+                return Some(total);
+            }
+            if distance % period != steps % period {
+                continue;
+            }
+            periods.push(total);
+            if let [.., a, b, c, d, e] = periods[..]
+                && [c - 2 * b + a, d - 2 * c + b] == [e - 2 * d + c; 2]
+            {
+                let growth = e - d;
+                let acceleration = e - 2 * d + c;
+                let n = (steps - distance) / period;
+                return Some(e + n * growth + acceleration * n * (n + 1) / 2);
+            }
+        }
+        None
     }
 }
 
+/// The periods to walk before giving up on the plots reached growing steadily.
+const SETTLE: i64 = 10;
+
 impl Day {
-    fn take_step(&self, visited: &HashSet<Vector>, new: &HashSet<Vector>) -> HashSet<Vector> {
-        let mut next = HashSet::new();
-        for &pos in new {
-            for a in DIRS {
-                let pos = pos + a;
-                if !self.grid.contains(pos.rem_euclid(self.size)) && !visited.contains(&pos) {
-                    next.insert(pos);
-                }
-            }
-        }
-        next
+    /// How many plots are first reached after each number of steps up to `radius`, on the
+    /// endlessly tiled map.
+    fn layers(&self, radius: i64) -> impl Iterator<Item = i64> + '_ {
+        let map = self.grid.tiled();
+        let corner = self.start - Vector::new(radius, radius);
+        let mut seen = VectorSet::new(Vector::new(2 * radius + 1, 2 * radius + 1));
+        seen.insert(self.start - corner);
+        let mut current = vec![self.start];
+        let layers = iter::from_fn(move || {
+            let plots = current.len() as i64;
+            let neighbors = current.iter().flat_map(|&pos| map.neighbors(pos));
+            current = neighbors
+                .filter(|&(pos, &plot)| {
+                    plot == Plot::Garden && seen.insert(pos - corner) == Some(true)
+                })
+                .map(|(pos, _)| pos)
+                .collect();
+            Some(plots)
+        });
+        layers.take(radius as usize + 1)
     }
 }
 
@@ -4,7 +4,7 @@ use std::{
 };
 use utils::prelude::*;
 
-use utils::{vector::Vector, vector3::Vector3};
+use utils::{error::column, vector::Vector, vector3::Vector3};
 
 pub struct Day {
     #[allow(dead_code)]
@@ -24,30 +24,42 @@ fn aabb(a: Block, b: Block) -> bool {
     Vector::aabb((a.0.xy(), a.1.xy()), (b.0.xy(), b.1.xy()))
 }
 
+fn parse_corner(index: usize, line: &str, corner: &str) -> ParseResult<Vector3> {
+    let coords: Vec<i64> = corner
+        .split(',')
+        .map(|n| parse_field(index, line, n))
+        .collect::<ParseResult<_>>()?;
+    let [x, y, z] = coords[..] else {
+        return Err(ParseError::at(
+            index,
+            column(line, corner),
+            "expected `x,y,z`",
+        ));
+    };
+    Ok(Vector3::new(x, y, z))
+}
+
 impl Solution<i64, i64> for Day {
     fn meta() -> Meta<i64, i64> {
         Meta::<i64, i64> {
             input: include_str!("input.txt").to_string(),
-            sample_a: include_str!("input_sample.txt").to_string(),
-            sample_b: include_str!("input_sample.txt").to_string(),
-            answer_a: 5,
-            answer_b: 7,
+            samples_a: vec![Sample::new("sample", include_str!("input_sample.txt"), 5)],
+            samples_b: vec![Sample::new("sample", include_str!("input_sample.txt"), 7)],
         }
     }
 
-    fn new(raw: Vec<Rc<str>>) -> Self {
+    fn new(raw: Vec<Rc<str>>) -> ParseResult<Self> {
         let one = Vector3::new(1, 1, 1);
         let mut blocks: Vec<_> = raw
             .iter()
-            .map(|line| {
-                let (left, right) = line.split_once('~').unwrap();
-                let left: Vec<_> = left.split(',').map(|n| n.parse().unwrap()).collect();
-                let right: Vec<_> = right.split(',').map(|n| n.parse().unwrap()).collect();
-                let left = Vector3::new(left[0], left[1], left[2]);
-                let right = Vector3::new(right[0], right[1], right[2]);
-                (left, Vector3::raw_add(right, one))
+            .enumerate()
+            .map(|(index, line)| {
+                let (left, right) = line.split_once('~').context(index, "`~`")?;
+                let left = parse_corner(index, line, left)?;
+                let right = parse_corner(index, line, right)?;
+                Ok((left, Vector3::raw_add(right, one)))
             })
-            .collect();
+            .collect::<ParseResult<_>>()?;
         blocks.sort_by_key(|block| block.0.z);
 
         for a in 0..blocks.len() {
@@ -82,10 +94,10 @@ impl Solution<i64, i64> for Day {
             links.push(link);
         }
 
-        Self {
+        Ok(Self {
             raw: raw.clone(),
             blocks: links,
-        }
+        })
     }
 
     fn part_a(&self) -> Option<i64> {
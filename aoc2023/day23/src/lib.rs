@@ -3,6 +3,8 @@ use utils::prelude::*;
 
 use utils::{
     direction::{DIRS, Direction},
+    search,
+    trace::{self, Frame},
     vector::{Vector, VectorMap, VectorSet},
 };
 
@@ -25,33 +27,24 @@ impl Solution<i64, i64> for Day {
     fn meta() -> Meta<i64, i64> {
         Meta::<i64, i64> {
             input: include_str!("input.txt").to_string(),
-            sample_a: include_str!("input_sample.txt").to_string(),
-            sample_b: include_str!("input_sample.txt").to_string(),
-            answer_a: 94,
-            answer_b: 154,
+            samples_a: vec![Sample::new("sample", include_str!("input_sample.txt"), 94)],
+            samples_b: vec![Sample::new("sample", include_str!("input_sample.txt"), 154)],
         }
     }
 
-    fn new(raw: Vec<Rc<str>>) -> Self {
-        let size = Vector::new_usize(raw[0].len(), raw.len());
-        let mut grid = VectorMap::new(size);
-        let mut start = None;
-        for (y, line) in raw.iter().enumerate() {
-            for (x, c) in line.chars().enumerate() {
-                let pos = Vector::new_usize(x, y);
-                if y == 0 && c == '.' {
-                    start = Some(pos);
-                }
-                grid.insert(pos, c.to_string().parse().unwrap());
-            }
-        }
+    fn new(raw: Vec<Rc<str>>) -> ParseResult<Self> {
+        let (grid, _) = VectorMap::from_grid(&raw, &[])?;
+        let size = grid.size();
+        let start = (0..size.x)
+            .map(|x| Vector::new(x, 0))
+            .find(|&pos| matches!(grid.get(pos), Some(Tile::Path)));
 
-        Self {
+        Ok(Self {
             raw: raw.clone(),
             grid,
-            start: start.unwrap(),
+            start: start.ok_or_else(|| ParseError::on_line(0, "missing start `.`"))?,
             size,
-        }
+        })
     }
 
     fn part_a(&self) -> Option<i64> {
@@ -73,7 +66,7 @@ impl Solution<i64, i64> for Day {
             if !set.insert(pos).unwrap() {
                 continue;
             }
-            if let Some(last) = seen.get(pos)
+            if let Some(&last) = seen.get(pos)
                 && last > distance
             {
                 continue;
@@ -82,6 +75,7 @@ impl Solution<i64, i64> for Day {
 
             if pos.y == self.size.y - 1 && distance > max {
                 max = distance;
+                trace::emit(|| self.frame("longest hike so far", &set, distance, heap.len()));
                 continue;
             }
 
@@ -99,7 +93,7 @@ impl Solution<i64, i64> for Day {
                     }
                 }
                 Some(Tile::Slope(slope)) => {
-                    next.push(slope);
+                    next.push(*slope);
                 }
                 _ => {}
             }
@@ -127,45 +121,16 @@ impl Solution<i64, i64> for Day {
 
     fn part_b(&self) -> Option<i64> {
         let mut graph: VectorMap<HashSet<(i64, Vector)>> = VectorMap::new(self.size);
-        let mut deque: VecDeque<Vector> = VecDeque::from([self.start]);
-        while let Some(start) = deque.pop_front() {
-            if graph.contains(start) {
-                continue;
-            }
-            let mut paths: HashSet<(i64, Vector)> = HashSet::new();
-            let mut path: VecDeque<(i64, Vector, Option<Direction>)> = VecDeque::new();
-            for dir in DIRS {
-                match self.grid.get(start + dir) {
-                    None | Some(Tile::Forest) => {}
-                    _ => path.push_back((0, start + dir, Some(dir))),
-                }
-            }
-            while let Some((dist, pos, last_dir)) = path.pop_front() {
-                let mut next: Vec<Direction> = Vec::new();
-                for dir in DIRS {
-                    if Some(dir.flip()) == last_dir {
-                        continue;
-                    }
-                    match self.grid.get(pos + dir) {
-                        None | Some(Tile::Forest) => {}
-                        _ => {
-                            next.push(dir);
-                            if (pos + dir).y == self.size.y - 1 {
-                                paths.insert((dist + 1, pos + dir));
-                                continue;
-                            }
-                        }
-                    }
-                }
-                if next.len() == 1 {
-                    path.push_back((dist + 1, pos + next[0], Some(next[0])));
-                } else if !next.is_empty() {
-                    paths.insert((dist + 1, pos));
-                    deque.push_back(pos);
-                }
-            }
-            graph.insert(start, paths);
-        }
+        search::flood_fill(self.start, |junction| {
+            let corridors = self.corridors(junction);
+            let next = corridors
+                .iter()
+                .map(|&(_, pos)| pos)
+                .filter(|pos| pos.y != self.size.y - 1)
+                .collect::<Vec<_>>();
+            graph.insert(junction, corridors);
+            next
+        });
 
         let mut heap: BinaryHeap<State> = BinaryHeap::from([State {
             distance: 0,
@@ -188,7 +153,14 @@ impl Solution<i64, i64> for Day {
             if pos.y == self.size.y - 1 {
                 if distance > max {
                     max = distance;
-                    println!("{max}");
+                    trace::emit(|| {
+                        self.frame(
+                            "longest hike so far, via junctions",
+                            &set,
+                            distance,
+                            heap.len(),
+                        )
+                    });
                 }
                 continue;
             }
@@ -196,7 +168,7 @@ impl Solution<i64, i64> for Day {
             let mut next: Vec<(i64, Vector)> = Vec::new();
             for node in graph.get(pos).unwrap() {
                 if !set.contains(node.1) {
-                    next.push(node);
+                    next.push(*node);
                 }
             }
 
@@ -223,18 +195,78 @@ impl Solution<i64, i64> for Day {
     }
 }
 
-impl std::str::FromStr for Tile {
-    type Err = ();
+impl Day {
+    /// The length of each corridor leaving `junction` and the junction, or end, it leads to.
+    fn corridors(&self, junction: Vector) -> HashSet<(i64, Vector)> {
+        let mut paths: HashSet<(i64, Vector)> = HashSet::new();
+        let mut path: VecDeque<(i64, Vector, Option<Direction>)> = VecDeque::new();
+        for dir in DIRS {
+            match self.grid.get(junction + dir) {
+                None | Some(Tile::Forest) => {}
+                _ => path.push_back((0, junction + dir, Some(dir))),
+            }
+        }
+        while let Some((dist, pos, last_dir)) = path.pop_front() {
+            let mut next: Vec<Direction> = Vec::new();
+            for dir in DIRS {
+                if Some(dir.flip()) == last_dir {
+                    continue;
+                }
+                match self.grid.get(pos + dir) {
+                    None | Some(Tile::Forest) => {}
+                    _ => {
+                        next.push(dir);
+                        if (pos + dir).y == self.size.y - 1 {
+                            paths.insert((dist + 1, pos + dir));
+                            continue;
+                        }
+                    }
+                }
+            }
+            if next.len() == 1 {
+                path.push_back((dist + 1, pos + next[0], Some(next[0])));
+            } else if !next.is_empty() {
+                paths.insert((dist + 1, pos));
+            }
+        }
+        paths
+    }
+
+    /// The trails with the hike's visited cells highlighted.
+    fn frame(&self, label: &str, visited: &VectorSet, distance: i64, queued: usize) -> Frame {
+        Frame::new(label)
+            .map(&self.grid, ' ', |tile| tile.symbol())
+            .highlight(visited.iter())
+            .counter("distance", distance)
+            .counter("queued", queued)
+    }
+}
+
+impl Tile {
+    fn symbol(self) -> char {
+        match self {
+            Self::Forest => '#',
+            Self::Path => '.',
+            Self::Slope(Direction::North) => '^',
+            Self::Slope(Direction::South) => 'v',
+            Self::Slope(Direction::East) => '>',
+            Self::Slope(Direction::West) => '<',
+        }
+    }
+}
+
+impl TryFrom<char> for Tile {
+    type Error = &'static str;
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s {
-            "#" => Ok(Self::Forest),
-            "." => Ok(Self::Path),
-            "^" => Ok(Self::Slope(Direction::North)),
-            "v" => Ok(Self::Slope(Direction::South)),
-            ">" => Ok(Self::Slope(Direction::East)),
-            "<" => Ok(Self::Slope(Direction::West)),
-            _ => Err(()),
+    fn try_from(c: char) -> Result<Self, Self::Error> {
+        match c {
+            '#' => Ok(Self::Forest),
+            '.' => Ok(Self::Path),
+            '^' => Ok(Self::Slope(Direction::North)),
+            'v' => Ok(Self::Slope(Direction::South)),
+            '>' => Ok(Self::Slope(Direction::East)),
+            '<' => Ok(Self::Slope(Direction::West)),
+            _ => Err("not a trail tile"),
         }
     }
 }
@@ -12,25 +12,27 @@ impl Solution<i64, i64> for Day {
     fn meta() -> Meta<i64, i64> {
         Meta::<i64, i64> {
             input: include_str!("input.txt").to_string(),
-            sample_a: include_str!("input_sample.txt").to_string(),
-            sample_b: include_str!("input_sample.txt").to_string(),
-            answer_a: 11,
-            answer_b: 31,
+            samples_a: vec![Sample::new("sample", include_str!("input_sample.txt"), 11)],
+            samples_b: vec![Sample::new("sample", include_str!("input_sample.txt"), 31)],
         }
     }
 
-    fn new(raw: Vec<Rc<str>>) -> Self {
-        Self {
-            raw: raw.clone(),
-            left: raw
-                .iter()
-                .map(|s| s.split_whitespace().next().unwrap().parse().unwrap())
-                .collect(),
-            right: raw
-                .iter()
-                .map(|s| s.split_whitespace().nth(1).unwrap().parse().unwrap())
-                .collect(),
+    fn new(raw: Vec<Rc<str>>) -> ParseResult<Self> {
+        let mut left = Vec::new();
+        let mut right = Vec::new();
+        for (index, line) in raw.iter().enumerate() {
+            let mut numbers = line.split_whitespace();
+            let a = numbers.next().context(index, "left number")?;
+            let b = numbers.next().context(index, "right number")?;
+            left.push(parse_field(index, line, a)?);
+            right.push(parse_field(index, line, b)?);
         }
+
+        Ok(Self {
+            raw: raw.clone(),
+            left,
+            right,
+        })
     }
 
     fn part_a(&self) -> Option<i64> {
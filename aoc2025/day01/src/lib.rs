@@ -10,21 +10,30 @@ impl Solution<i64, i64> for Day {
     fn meta() -> Meta<i64, i64> {
         Meta::<i64, i64> {
             input: include_str!("input.txt").to_string(),
-            sample_a: include_str!("input_sample.txt").to_string(),
-            sample_b: include_str!("input_sample.txt").to_string(),
-            answer_a: 3,
-            answer_b: 6,
+            samples_a: vec![Sample::new("sample", include_str!("input_sample.txt"), 3)],
+            samples_b: vec![Sample::new("sample", include_str!("input_sample.txt"), 6)],
         }
     }
 
-    fn new(raw: Vec<Rc<str>>) -> Self {
+    fn new(raw: Vec<Rc<str>>) -> ParseResult<Self> {
         let mut rotations = Vec::new();
-        for line in &raw {
-            let (sign, value) = line.split_at(1);
-            let value: i64 = value.parse().unwrap();
-            rotations.push(value * if sign == "R" { 1 } else { -1 });
+        for (index, line) in raw.iter().enumerate() {
+            let (sign, value) = line.split_at_checked(1).context(index, "a rotation")?;
+            let value: i64 = parse_field(index, line, value)?;
+            let sign = match sign {
+                "R" => 1,
+                "L" => -1,
+                _ => {
+                    return Err(ParseError::at(
+                        index,
+                        0,
+                        format!("invalid direction `{sign}`"),
+                    ));
+                }
+            };
+            rotations.push(value * sign);
         }
-        Self { raw, rotations }
+        Ok(Self { raw, rotations })
     }
 
     fn part_a(&self) -> Option<i64> {
@@ -72,28 +81,28 @@ mod tests {
     #[test]
     fn r1000() {
         let input = ["R1000"];
-        let solution = Day::new(input.iter().map(|&s| s.into()).collect());
+        let solution = Day::new(input.iter().map(|&s| s.into()).collect()).unwrap();
         assert_eq!(solution.part_b(), Some(10));
     }
 
     #[test]
     fn r50() {
         let input = ["R50", "R100"];
-        let solution = Day::new(input.iter().map(|&s| s.into()).collect());
+        let solution = Day::new(input.iter().map(|&s| s.into()).collect()).unwrap();
         assert_eq!(solution.part_b(), Some(2));
     }
 
     #[test]
     fn l1000() {
         let input = ["R50", "L1000"];
-        let solution = Day::new(input.iter().map(|&s| s.into()).collect());
+        let solution = Day::new(input.iter().map(|&s| s.into()).collect()).unwrap();
         assert_eq!(solution.part_b(), Some(11));
     }
 
     #[test]
     fn r50_l1_r1() {
         let input = ["R50", "L1", "R1"];
-        let solution = Day::new(input.iter().map(|&s| s.into()).collect());
+        let solution = Day::new(input.iter().map(|&s| s.into()).collect()).unwrap();
         assert_eq!(solution.part_b(), Some(2));
     }
 }
@@ -1,6 +1,6 @@
 use std::ops::RangeInclusive;
 
-use utils::prelude::*;
+use utils::{parse::Field, prelude::*};
 
 pub struct Day {
     #[allow(dead_code)]
@@ -13,26 +13,24 @@ impl Solution<i64, i64> for Day {
     fn meta() -> Meta<i64, i64> {
         Meta::<i64, i64> {
             input: include_str!("input.txt").to_string(),
-            sample_a: include_str!("input_sample.txt").to_string(),
-            sample_b: include_str!("input_sample.txt").to_string(),
-            answer_a: 1227775554,
-            answer_b: 4174379265,
+            samples_a: vec![Sample::new(
+                "sample",
+                include_str!("input_sample.txt"),
+                1227775554,
+            )],
+            samples_b: vec![Sample::new(
+                "sample",
+                include_str!("input_sample.txt"),
+                4174379265,
+            )],
         }
     }
 
-    fn new(raw: Vec<Rc<str>>) -> Self {
-        let mut ranges = Vec::new();
-        let mut max = 0;
+    fn new(raw: Vec<Rc<str>>) -> ParseResult<Self> {
+        let ranges: Vec<RangeInclusive<i64>> = Field::new(0, &raw[0]).list(",")?;
+        let max = ranges.iter().map(|range| *range.end()).max().unwrap_or(0);
 
-        for range in raw[0].split(',') {
-            let (start, end) = range.split_once('-').unwrap();
-            let start = start.parse().unwrap();
-            let end = end.parse().unwrap();
-            max = max.max(end);
-            ranges.push(start..=end);
-        }
-
-        Self { raw, ranges, max }
+        Ok(Self { raw, ranges, max })
     }
 
     fn part_a(&self) -> Option<i64> {
@@ -10,24 +10,32 @@ impl Solution<u64, u64> for Day {
     fn meta() -> Meta<u64, u64> {
         Meta::<u64, u64> {
             input: include_str!("input.txt").to_string(),
-            sample_a: include_str!("input_sample.txt").to_string(),
-            sample_b: include_str!("input_sample.txt").to_string(),
-            answer_a: 357,
-            answer_b: 3121910778619,
+            samples_a: vec![Sample::new("sample", include_str!("input_sample.txt"), 357)],
+            samples_b: vec![Sample::new(
+                "sample",
+                include_str!("input_sample.txt"),
+                3121910778619,
+            )],
         }
     }
 
-    fn new(raw: Vec<Rc<str>>) -> Self {
+    fn new(raw: Vec<Rc<str>>) -> ParseResult<Self> {
         let banks = raw
             .iter()
-            .map(|line| {
+            .enumerate()
+            .map(|(y, line)| {
                 line.chars()
-                    .map(|c| c.to_digit(10).unwrap() as u64)
+                    .enumerate()
+                    .map(|(x, c)| {
+                        c.to_digit(10)
+                            .map(|digit| digit as u64)
+                            .ok_or_else(|| ParseError::at(y, x, format!("invalid digit `{c}`")))
+                    })
                     .collect()
             })
-            .collect();
+            .collect::<ParseResult<_>>()?;
 
-        Self { raw, banks }
+        Ok(Self { raw, banks })
     }
 
     fn part_a(&self) -> Option<u64> {
@@ -38,12 +46,14 @@ impl Solution<u64, u64> for Day {
             let mut last = 0;
 
             for i in 0..2 {
-                let max = bank[last..bank.len() - (1 - i)]
+                // Leaves a battery for each digit still to pick, if there are enough.
+                let end = (bank.len() + i).checked_sub(1)?;
+                let max = bank
+                    .get(last..end)?
                     .iter()
                     .enumerate()
                     .rev()
-                    .max_by_key(|m| m.1)
-                    .unwrap();
+                    .max_by_key(|m| m.1)?;
 
                 last += max.0 + 1;
                 joltage = joltage * 10 + max.1;
@@ -63,12 +73,13 @@ impl Solution<u64, u64> for Day {
             let mut last = 0;
 
             for i in 0..12 {
-                let max = bank[last..bank.len() - (11 - i)]
+                let end = (bank.len() + i).checked_sub(11)?;
+                let max = bank
+                    .get(last..end)?
                     .iter()
                     .enumerate()
                     .rev()
-                    .max_by_key(|m| m.1)
-                    .unwrap();
+                    .max_by_key(|m| m.1)?;
 
                 last += max.0 + 1;
                 joltage = joltage * 10 + max.1;
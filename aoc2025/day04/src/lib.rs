@@ -14,17 +14,15 @@ impl Solution<usize, usize> for Day {
     fn meta() -> Meta<usize, usize> {
         Meta::<usize, usize> {
             input: include_str!("input.txt").to_string(),
-            sample_a: include_str!("input_sample.txt").to_string(),
-            sample_b: include_str!("input_sample.txt").to_string(),
-            answer_a: 13,
-            answer_b: 43,
+            samples_a: vec![Sample::new("sample", include_str!("input_sample.txt"), 13)],
+            samples_b: vec![Sample::new("sample", include_str!("input_sample.txt"), 43)],
         }
     }
 
-    fn new(raw: Vec<Rc<str>>) -> Self {
+    fn new(raw: Vec<Rc<str>>) -> ParseResult<Self> {
         let paper = VectorSet::from_grid(&raw, '@');
         let size = Vector::new_usize(raw[0].len(), raw.len());
-        Self { raw, paper, size }
+        Ok(Self { raw, paper, size })
     }
 
     fn part_a(&self) -> Option<usize> {
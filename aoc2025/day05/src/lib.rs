@@ -15,31 +15,36 @@ impl Solution<i64, i64> for Day {
     fn meta() -> Meta<i64, i64> {
         Meta::<i64, i64> {
             input: include_str!("input.txt").to_string(),
-            sample_a: include_str!("input_sample.txt").to_string(),
-            sample_b: include_str!("input_sample.txt").to_string(),
-            answer_a: 3,
-            answer_b: 14,
+            samples_a: vec![Sample::new("sample", include_str!("input_sample.txt"), 3)],
+            samples_b: vec![Sample::new("sample", include_str!("input_sample.txt"), 14)],
         }
     }
 
-    fn new(raw: Vec<Rc<str>>) -> Self {
-        let (fresh, available) = raw.split_once(|line| line.is_empty()).unwrap();
-        let available = available.iter().map(|line| line.parse().unwrap()).collect();
+    fn new(raw: Vec<Rc<str>>) -> ParseResult<Self> {
+        let (fresh, available) = raw
+            .split_once(|line| line.is_empty())
+            .context(raw.len(), "available ingredients")?;
+        let available = available
+            .iter()
+            .enumerate()
+            .map(|(index, line)| parse_field(fresh.len() + 1 + index, line, line))
+            .collect::<ParseResult<_>>()?;
         let fresh = fresh
             .iter()
-            .map(|line| {
-                let (start, end) = line.split_once('-').unwrap();
-                let start = start.parse().unwrap();
-                let end = end.parse::<i64>().unwrap() + 1;
-                start..end
+            .enumerate()
+            .map(|(index, line)| {
+                let (start, end) = line.split_once('-').context(index, "a `-` range")?;
+                let start = parse_field(index, line, start)?;
+                let end = parse_field::<i64>(index, line, end)? + 1;
+                Ok(start..end)
             })
-            .collect();
+            .collect::<ParseResult<_>>()?;
 
-        Self {
+        Ok(Self {
             raw,
             fresh,
             available,
-        }
+        })
     }
 
     fn part_a(&self) -> Option<i64> {
@@ -48,7 +53,9 @@ impl Solution<i64, i64> for Day {
         available.sort();
 
         let mut count = 0;
-        let mut range = ranges.next().unwrap();
+        let Some(mut range) = ranges.next() else {
+            return Some(0);
+        };
 
         for a in available {
             while a > range.end {
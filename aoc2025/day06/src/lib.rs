@@ -32,33 +32,59 @@ impl Solution<i64, i64> for Day {
     fn meta() -> Meta<i64, i64> {
         Meta::<i64, i64> {
             input: include_str!("input.txt").to_string(),
-            sample_a: include_str!("input_sample.txt").to_string(),
-            sample_b: include_str!("input_sample.txt").to_string(),
-            answer_a: 4277556,
-            answer_b: 3263827,
+            samples_a: vec![Sample::new(
+                "sample",
+                include_str!("input_sample.txt"),
+                4277556,
+            )],
+            samples_b: vec![Sample::new(
+                "sample",
+                include_str!("input_sample.txt"),
+                3263827,
+            )],
         }
     }
 
-    fn new(raw: Vec<Rc<str>>) -> Self {
+    fn new(raw: Vec<Rc<str>>) -> ParseResult<Self> {
         let mut columns = Vec::new();
+        if raw.len() < 2 {
+            return Err(ParseError::on_line(
+                0,
+                "missing numbers above the operations",
+            ));
+        }
 
         let numbers: Vec<Vec<Option<i64>>> = raw
             .iter()
             .take(raw.len() - 1)
-            .map(|line| {
+            .enumerate()
+            .map(|(y, line)| {
                 line.chars()
-                    .map(|c| match c != ' ' {
-                        true => Some(c.to_string().parse::<i64>().unwrap()),
-                        false => None,
+                    .enumerate()
+                    .map(|(x, c)| match c != ' ' {
+                        true => c
+                            .to_digit(10)
+                            .map(|digit| Some(digit as i64))
+                            .ok_or_else(|| ParseError::at(y, x, format!("invalid digit `{c}`"))),
+                        false => Ok(None),
                     })
                     .collect()
             })
-            .collect();
+            .collect::<ParseResult<_>>()?;
 
-        for op in raw.last().unwrap().split_whitespace() {
+        let last = raw.len() - 1;
+        for op in raw[last].split_whitespace() {
             let op = match op {
                 "+" => Operation::Plus,
-                _ => Operation::Star,
+                "*" => Operation::Star,
+                _ => {
+                    let column = utils::error::column(&raw[last], op);
+                    return Err(ParseError::at(
+                        last,
+                        column,
+                        format!("invalid operation `{op}`"),
+                    ));
+                }
             };
 
             columns.push(Column {
@@ -67,11 +93,24 @@ impl Solution<i64, i64> for Day {
             })
         }
 
-        Self {
+        for (index, line) in raw.iter().enumerate().take(last) {
+            let count = line.split_whitespace().count();
+            if count != columns.len() {
+                return Err(ParseError::on_line(
+                    index,
+                    format!(
+                        "expected {} numbers, one per operation, found {count}",
+                        columns.len()
+                    ),
+                ));
+            }
+        }
+
+        Ok(Self {
             raw,
             columns,
             numbers,
-        }
+        })
     }
 
     fn part_a(&self) -> Option<i64> {
@@ -80,7 +119,7 @@ impl Solution<i64, i64> for Day {
         for line in self.raw.iter().take(self.raw.len() - 1) {
             let cs = line.split_whitespace();
             for (column, number) in cs.enumerate() {
-                columns[column].rows.push(number.parse().unwrap());
+                columns[column].rows.push(number.parse().ok()?);
             }
         }
 
@@ -91,16 +130,17 @@ impl Solution<i64, i64> for Day {
         let mut columns = self.columns.clone();
 
         let mut column = 0;
-        for c in 0..self.numbers[0].len() {
+        let width = self.numbers.iter().map(Vec::len).max().unwrap_or_default();
+        for c in 0..width {
             let mut number = 0;
             for row in &self.numbers {
-                if let Some(n) = row[c] {
+                if let Some(&Some(n)) = row.get(c) {
                     number = number * 10 + n;
                 }
             }
 
             match number != 0 {
-                true => columns[column].rows.push(number),
+                true => columns.get_mut(column)?.rows.push(number),
                 false => column += 1,
             }
         }
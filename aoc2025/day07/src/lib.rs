@@ -15,24 +15,25 @@ impl Solution<usize, usize> for Day {
     fn meta() -> Meta<usize, usize> {
         Meta::<usize, usize> {
             input: include_str!("input.txt").to_string(),
-            sample_a: include_str!("input_sample.txt").to_string(),
-            sample_b: include_str!("input_sample.txt").to_string(),
-            answer_a: 21,
-            answer_b: 40,
+            samples_a: vec![Sample::new("sample", include_str!("input_sample.txt"), 21)],
+            samples_b: vec![Sample::new("sample", include_str!("input_sample.txt"), 40)],
         }
     }
 
-    fn new(raw: Vec<Rc<str>>) -> Self {
+    fn new(raw: Vec<Rc<str>>) -> ParseResult<Self> {
         let map = VectorSet::from_grid(&raw, '^');
         let size = Vector::new_usize(raw[0].len(), raw.len());
-        let start = raw[0].chars().position(|c| c == 'S').unwrap() as i64;
+        let start = raw[0]
+            .chars()
+            .position(|c| c == 'S')
+            .context(0, "start `S`")? as i64;
 
-        Self {
+        Ok(Self {
             raw,
             map,
             size,
             start,
-        }
+        })
     }
 
     fn part_a(&self) -> Option<usize> {
@@ -65,18 +66,18 @@ impl Solution<usize, usize> for Day {
 
         for y in 0..self.size.y {
             for x in 0..self.size.x {
-                let Some(c) = beams.get(Vector::new(x, y)) else {
+                let Some(&c) = beams.get(Vector::new(x, y)) else {
                     continue;
                 };
 
                 if self.map.contains(Vector::new(x, y)) {
                     let pos = Vector::new(x - 1, y + 1);
-                    beams.insert(pos, beams.get(pos).unwrap_or_default() + c);
+                    beams.insert(pos, beams.get(pos).copied().unwrap_or_default() + c);
                     let pos = Vector::new(x + 1, y + 1);
-                    beams.insert(pos, beams.get(pos).unwrap_or_default() + c);
+                    beams.insert(pos, beams.get(pos).copied().unwrap_or_default() + c);
                 } else {
                     let pos = Vector::new(x, y + 1);
-                    beams.insert(pos, beams.get(pos).unwrap_or_default() + c);
+                    beams.insert(pos, beams.get(pos).copied().unwrap_or_default() + c);
                 }
             }
         }
@@ -86,6 +87,7 @@ impl Solution<usize, usize> for Day {
                 .map(|x| {
                     beams
                         .get(Vector::new(x, self.size.y - 1))
+                        .copied()
                         .unwrap_or_default()
                 })
                 .sum(),
@@ -1,34 +1,48 @@
 use std::collections::HashMap;
 
-use utils::{disjointset::DisjointSet, prelude::*, vector3::Vector3};
+use utils::{disjointset::DisjointSet, parse::parse_lines, prelude::*, vector3::Vector3};
 
 pub struct Day {
     #[allow(dead_code)]
     raw: Vec<Rc<str>>,
     boxes: Vec<Vector3>,
     distances: Vec<((usize, usize), i64)>,
+    params: Params,
 }
 
-impl Solution<i64, i64> for Day {
-    fn meta() -> Meta<i64, i64> {
-        Meta::<i64, i64> {
+#[derive(Clone)]
+pub struct Params {
+    connections: usize,
+}
+
+impl Default for Params {
+    fn default() -> Self {
+        Self { connections: 1000 }
+    }
+}
+
+impl Solution<i64, i64, Params> for Day {
+    fn meta() -> Meta<i64, i64, Params> {
+        Meta::<i64, i64, Params> {
             input: include_str!("input.txt").to_string(),
-            sample_a: include_str!("input_sample.txt").to_string(),
-            sample_b: include_str!("input_sample.txt").to_string(),
-            answer_a: 40,
-            answer_b: 25272,
+            samples_a: vec![
+                Sample::new("sample", include_str!("input_sample.txt"), 40)
+                    .with_params(Params { connections: 10 }),
+            ],
+            samples_b: vec![Sample::new(
+                "sample",
+                include_str!("input_sample.txt"),
+                25272,
+            )],
         }
     }
 
-    fn new(raw: Vec<Rc<str>>) -> Self {
-        let mut boxes = Vec::new();
-        for line in &raw {
-            let mut parts = line.split(',');
-            let x = parts.next().unwrap().parse().unwrap();
-            let y = parts.next().unwrap().parse().unwrap();
-            let z = parts.next().unwrap().parse().unwrap();
-            boxes.push(Vector3::new(x, y, z));
-        }
+    fn new(raw: Vec<Rc<str>>) -> ParseResult<Self> {
+        Self::new_with(raw, &Params::default())
+    }
+
+    fn new_with(raw: Vec<Rc<str>>, params: &Params) -> ParseResult<Self> {
+        let boxes: Vec<Vector3> = parse_lines(&raw)?;
 
         let mut map: HashMap<(usize, usize), i64> = HashMap::new();
         for (i, &a) in boxes[..boxes.len() - 1].iter().enumerate() {
@@ -41,19 +55,19 @@ impl Solution<i64, i64> for Day {
         let mut distances: Vec<((usize, usize), i64)> = map.into_iter().collect();
         distances.sort_by_key(|&(_, distance)| distance);
 
-        Self {
+        Ok(Self {
             raw,
             boxes,
             distances,
-        }
+            params: params.clone(),
+        })
     }
 
     fn part_a(&self) -> Option<i64> {
         let length = self.boxes.len();
-        let connections = if length < 30 { 10 } else { 1000 };
         let mut set = DisjointSet::new(length);
 
-        for &((a, b), _) in self.distances.iter().take(connections) {
+        for &((a, b), _) in self.distances.iter().take(self.params.connections) {
             set.union(a, b);
         }
 
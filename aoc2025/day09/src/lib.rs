@@ -15,19 +15,17 @@ impl Solution<i64, i64> for Day {
     fn meta() -> Meta<i64, i64> {
         Meta::<i64, i64> {
             input: include_str!("input.txt").to_string(),
-            sample_a: include_str!("input_sample.txt").to_string(),
-            sample_b: include_str!("input_sample.txt").to_string(),
-            answer_a: 50,
-            answer_b: 24,
+            samples_a: vec![Sample::new("sample", include_str!("input_sample.txt"), 50)],
+            samples_b: vec![Sample::new("sample", include_str!("input_sample.txt"), 24)],
         }
     }
 
-    fn new(raw: Vec<Rc<str>>) -> Self {
+    fn new(raw: Vec<Rc<str>>) -> ParseResult<Self> {
         let mut tiles = Vec::new();
-        for line in &raw {
-            let (x, y) = line.split_once(',').unwrap();
-            let x = x.parse().unwrap();
-            let y = y.parse().unwrap();
+        for (index, line) in raw.iter().enumerate() {
+            let (x, y) = line.split_once(',').context(index, "`x,y`")?;
+            let x = parse_field(index, line, x)?;
+            let y = parse_field(index, line, y)?;
             tiles.push(Vector::new(x, y));
         }
 
@@ -35,14 +33,20 @@ impl Solution<i64, i64> for Day {
             .windows(2)
             .map(|window| (window[0], window[1]))
             .chain([(*tiles.last().unwrap(), *tiles.first().unwrap())])
-            .map(|(a, b)| (a, b, Direction::try_from(b - a).unwrap().to_edge()))
-            .collect();
-
-        Self {
+            .enumerate()
+            .map(|(index, (a, b))| {
+                let direction = Direction::try_from(b - a);
+                let direction = direction
+                    .map_err(|_| ParseError::on_line(index, "tiles must share a row or column"))?;
+                Ok((a, b, direction.to_edge()))
+            })
+            .collect::<ParseResult<_>>()?;
+
+        Ok(Self {
             raw,
             tiles,
             segments,
-        }
+        })
     }
 
     fn part_a(&self) -> Option<i64> {
@@ -21,25 +21,24 @@ impl Solution<usize, usize> for Day {
     fn meta() -> Meta<usize, usize> {
         Meta::<usize, usize> {
             input: include_str!("input.txt").to_string(),
-            sample_a: include_str!("input_sample.txt").to_string(),
-            sample_b: include_str!("input_sample.txt").to_string(),
-            answer_a: 7,
-            answer_b: 33,
+            samples_a: vec![Sample::new("sample", include_str!("input_sample.txt"), 7)],
+            samples_b: vec![Sample::new("sample", include_str!("input_sample.txt"), 33)],
         }
     }
 
-    fn new(raw: Vec<Rc<str>>) -> Self {
+    fn new(raw: Vec<Rc<str>>) -> ParseResult<Self> {
         let mut machines = Vec::new();
 
-        for line in &raw {
+        for (index, line) in raw.iter().enumerate() {
             let parts: Vec<_> = line.split_whitespace().collect();
-            let raw_lights: Vec<_> = parts[0].chars().collect();
-            let raw_requirements = parts[parts.len() - 1];
-            let raw_requirements = &raw_requirements[1..raw_requirements.len() - 1];
-            let raw_buttons: Vec<_> = parts.iter().skip(1).take(parts.len() - 2).collect();
+            let (first, rest) = parts.split_first().context(index, "lights")?;
+            let (last, raw_buttons) = rest.split_last().context(index, "joltages")?;
+            let raw_lights = between(first, '[', ']').context(index, "`[lights]`")?;
+            let raw_lights: Vec<_> = raw_lights.chars().collect();
+            let raw_requirements = between(last, '{', '}').context(index, "`{joltages}`")?;
 
             let mut lights = 0;
-            for &light in raw_lights[1..raw_lights.len() - 1].iter().rev() {
+            for &light in raw_lights.iter().rev() {
                 lights <<= 1;
                 if light == '#' {
                     lights += 1;
@@ -48,25 +47,19 @@ impl Solution<usize, usize> for Day {
 
             let mut requirements = Vec::new();
             for requirement in raw_requirements.split(',') {
-                requirements.push(requirement.parse().unwrap());
+                requirements.push(parse_field(index, line, requirement)?);
             }
 
             let mut buttons = Vec::new();
             let mut schematics = Vec::new();
             for button in raw_buttons {
-                buttons.push(
-                    button[1..button.len() - 1]
-                        .split(',')
-                        .map(|n| n.parse().unwrap())
-                        .map(|n| 2usize.pow(n))
-                        .sum(),
-                );
-                schematics.push(
-                    button[1..button.len() - 1]
-                        .split(',')
-                        .map(|n| n.parse().unwrap())
-                        .collect(),
-                );
+                let schematic: Vec<usize> = between(button, '(', ')')
+                    .context(index, "`(button)`")?
+                    .split(',')
+                    .map(|n| parse_field(index, line, n))
+                    .collect::<ParseResult<_>>()?;
+                buttons.push(schematic.iter().map(|&n| 1 << n).sum());
+                schematics.push(schematic);
             }
 
             machines.push(Machine {
@@ -77,7 +70,7 @@ impl Solution<usize, usize> for Day {
             })
         }
 
-        Self { raw, machines }
+        Ok(Self { raw, machines })
     }
 
     fn part_a(&self) -> Option<usize> {
@@ -89,6 +82,10 @@ impl Solution<usize, usize> for Day {
     }
 }
 
+fn between(text: &str, open: char, close: char) -> Option<&str> {
+    text.strip_prefix(open)?.strip_suffix(close)
+}
+
 impl Machine {
     fn min_presses(&self) -> usize {
         (1..(1 << self.buttons.len()))
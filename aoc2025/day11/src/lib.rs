@@ -12,26 +12,29 @@ impl Solution<usize, usize> for Day {
     fn meta() -> Meta<usize, usize> {
         Meta::<usize, usize> {
             input: include_str!("input.txt").to_string(),
-            sample_a: include_str!("input_sample.txt").to_string(),
-            sample_b: include_str!("input_sample_b.txt").to_string(),
-            answer_a: 5,
-            answer_b: 2,
+            samples_a: vec![Sample::new("sample", include_str!("input_sample.txt"), 5)],
+            samples_b: vec![Sample::new(
+                "sample b",
+                include_str!("input_sample_b.txt"),
+                2,
+            )],
         }
     }
 
-    fn new(raw: Vec<Rc<str>>) -> Self {
+    fn new(raw: Vec<Rc<str>>) -> ParseResult<Self> {
         let devices = raw
             .iter()
-            .map(|line| {
-                let (name, connections) = line.split_once(": ").unwrap();
-                (
+            .enumerate()
+            .map(|(index, line)| {
+                let (name, connections) = line.split_once(": ").context(index, "`: `")?;
+                Ok((
                     name.into(),
                     connections.split_whitespace().map(|s| s.into()).collect(),
-                )
+                ))
             })
-            .collect();
+            .collect::<ParseResult<_>>()?;
 
-        Self { raw, devices }
+        Ok(Self { raw, devices })
     }
 
     fn part_a(&self) -> Option<usize> {
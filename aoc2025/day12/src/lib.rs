@@ -1,21 +1,24 @@
-use std::collections::{BTreeSet, BinaryHeap, HashMap};
+use std::collections::BTreeSet;
 
 use utils::{
+    parse::sections,
     prelude::*,
-    vector::{Vector, VectorSet},
+    trace::{self, Frame},
+    vector::{Vector, VectorMap, VectorSet},
 };
 
 pub struct Day {
     #[allow(dead_code)]
     raw: Vec<Rc<str>>,
-    #[allow(dead_code)]
     shapes: Vec<Shape>,
     regions: Vec<Region>,
 }
 
+/// The size of every present's grid.
+const SHAPE: Vector = Vector::new(3, 3);
+
 #[derive(Clone, Debug)]
 struct Shape {
-    size: Vector,
     orientations: Vec<BTreeSet<Vector>>,
     count: usize,
 }
@@ -30,51 +33,72 @@ impl Solution<usize, usize> for Day {
     fn meta() -> Meta<usize, usize> {
         Meta::<usize, usize> {
             input: include_str!("input.txt").to_string(),
-            sample_a: include_str!("input_sample.txt").to_string(),
-            sample_b: include_str!("input_sample.txt").to_string(),
-            answer_a: 2,
-            answer_b: 0,
+            samples_a: vec![Sample::new("sample", include_str!("input_sample.txt"), 2)],
+            samples_b: vec![Sample::new("sample", include_str!("input_sample.txt"), 0)],
         }
     }
 
-    fn new(raw: Vec<Rc<str>>) -> Self {
-        let mut shapes = Vec::new();
-        let chunks: Vec<_> = raw.split(|line| line.is_empty()).collect();
-        for chunk in &chunks[0..chunks.len() - 1] {
-            let shape = VectorSet::from_grid(&chunk[1..], '#');
-            shapes.push(Shape::new(&shape, Vector::new(3, 3)));
-        }
+    fn new(raw: Vec<Rc<str>>) -> ParseResult<Self> {
+        let sections = sections(&raw);
+        let (regions, shapes) = sections.split_last().context(0, "shapes and regions")?;
+        shapes
+            .first()
+            .context(regions.start, "shapes before the regions")?;
+        let shapes = shapes
+            .iter()
+            .map(|section| {
+                let header = section.header()?;
+                if !header.as_str().ends_with(':') {
+                    return Err(header.error("expected a header like `0:`"));
+                }
+                let grid = section
+                    .lines
+                    .get(1..)
+                    .filter(|grid| !grid.is_empty())
+                    .context(section.start, "shape below the header")?;
+                let shape = VectorSet::from_grid(grid, '#');
+                Ok(Shape::new(&shape, SHAPE))
+            })
+            .collect::<ParseResult<Vec<_>>>()?;
 
-        let mut regions = Vec::new();
-        for line in chunks[chunks.len() - 1] {
-            let (size, counts) = line.split_once(": ").unwrap();
-            let (x, y) = size.split_once('x').unwrap();
-            let size = Vector::new(x.parse().unwrap(), y.parse().unwrap());
-            let counts = counts
-                .split_whitespace()
-                .map(|n| n.parse().unwrap())
-                .collect();
-
-            regions.push(Region { size, counts });
-        }
+        let regions = regions
+            .fields()
+            .map(|field| {
+                let (index, line) = (field.line(), field.as_str());
+                let (size, counts) = line.split_once(": ").context(index, "`: `")?;
+                let (x, y) = size.split_once('x').context(index, "`WxH` size")?;
+                let size = Vector::new(parse_field(index, line, x)?, parse_field(index, line, y)?);
+                let counts = counts
+                    .split_whitespace()
+                    .map(|n| parse_field(index, line, n))
+                    .collect::<ParseResult<Vec<_>>>()?;
+                if counts.len() != shapes.len() {
+                    return Err(ParseError::on_line(
+                        index,
+                        format!(
+                            "expected {} counts, one per shape, found {}",
+                            shapes.len(),
+                            counts.len()
+                        ),
+                    ));
+                }
+                Ok(Region { size, counts })
+            })
+            .collect::<ParseResult<Vec<_>>>()?;
 
-        Self {
+        Ok(Self {
             raw,
             shapes,
             regions,
-        }
+        })
     }
 
     fn part_a(&self) -> Option<usize> {
-        let count = self
+        let fits = self
             .regions
             .iter()
-            .filter(|&region| {
-                region.size.area() as usize >= region.counts.iter().sum::<usize>() * 9
-            })
-            .count();
-
-        Some(count + (self.regions.len() < 4) as usize)
+            .filter(|region| region.fits(&self.shapes));
+        Some(fits.count())
     }
 
     fn part_b(&self) -> Option<usize> {
@@ -83,123 +107,113 @@ impl Solution<usize, usize> for Day {
 }
 
 impl Region {
-    #[allow(dead_code)]
-    fn can_fit(&self, shapes: &[Shape]) -> bool {
-        let mut needed = Vec::new();
-        for (index, &count) in self.counts.iter().enumerate() {
-            for _ in 0..count {
-                needed.push(shapes[index].clone());
-            }
-        }
-        let count: usize = needed.iter().map(|shape| shape.count).sum();
-        if count > self.size.area() as usize {
-            return false;
-        }
-
-        let mut queue = BinaryHeap::from([State {
-            grid: HashMap::new(),
-            index: 0,
-            orientation: 0,
-            pos: Vector::zero(),
-        }]);
-
-        while let Some(state) = queue.pop() {
-            let Some(shape) = needed.get(state.index) else {
-                pretty_print(&state.grid, self.size);
-                return true;
+    /// Whether the presents fit, only searching for a packing when counting cells can't tell.
+    fn fits(&self, shapes: &[Shape]) -> bool {
+        let cells = self.counts.iter().zip(shapes);
+        let cells = cells
+            .map(|(&count, shape)| count * shape.count)
+            .sum::<usize>();
+        let presents = self.counts.iter().sum::<usize>();
+        let blocks = (self.size.x / SHAPE.x) * (self.size.y / SHAPE.y);
+        if cells > self.size.area() as usize {
+            false
+        } else if presents <= blocks as usize {
+            // Every present gets a block of its own.
+            true
+        } else {
+            // Shapes turn every way, so search the region on its side if that makes rows shorter.
+            let size = match self.size.x <= self.size.y {
+                true => self.size,
+                false => Vector::new(self.size.y, self.size.x),
             };
-            let max_pos = self.size - shape.size; //- Vector::new(1, 1);
-            let Some(shape) = shape.orientations.get(state.orientation) else {
-                queue.push(state.shift_right());
-                continue;
+            let mut packing = Packing {
+                grid: VectorMap::new(size),
+                remaining: self.counts.clone(),
+                spare: self.size.area() as usize - cells,
             };
-
-            if state.pos.y > max_pos.y {
-                // Hit the bottom of the grid, continue onto next state in BFS
-                continue;
-            } else if state.pos.x > max_pos.x {
-                // Hit the right edge of the grid, carriage return onto BFS
-                queue.push(state.shift_down());
-                continue;
-            } else if shape
-                .iter()
-                .any(|&spot| state.grid.contains_key(&(state.pos + spot)))
-            {
-                // There was an overlap between the shape and the grid
-                queue.push(state.inc_orientation());
-                continue;
-            }
-
-            let mut new_grid = state.grid.clone();
-            for &spot in shape {
-                new_grid.insert(state.pos + spot, state.index);
-            }
-
-            queue.push(state.inc_index(new_grid));
-            queue.push(State {
-                grid: state.grid.clone(),
-                index: state.index,
-                orientation: state.orientation + 1,
-                pos: state.pos,
-            });
-            queue.push(state.shift_right());
+            packing.pack(shapes, 0)
         }
-
-        false
     }
 }
 
-#[derive(Eq, PartialEq)]
-struct State {
-    grid: HashMap<Vector, usize>,
-    index: usize,
-    orientation: usize,
-    pos: Vector,
+/// The presents placed so far while searching for a packing, and what's left to place.
+struct Packing {
+    /// The index of the shape covering each cell.
+    grid: VectorMap<usize>,
+    /// How many presents of each shape are still to place.
+    remaining: Vec<usize>,
+    /// How many more cells can stay empty.
+    spare: usize,
 }
 
-impl State {
-    fn shift_right(self) -> Self {
-        Self {
-            orientation: 0,
-            pos: Vector::new(self.pos.x + 1, self.pos.y),
-            ..self
+impl Packing {
+    /// Decides the cells in reading order from `cell`: each free one either holds the first cell
+    /// of a remaining present, or stays empty while there are cells to spare.
+    fn pack(&mut self, shapes: &[Shape], cell: i64) -> bool {
+        if self.remaining.iter().all(|&count| count == 0) {
+            trace::emit(|| self.frame("fits"));
+            return true;
         }
-    }
+        let size = self.grid.size();
+        let free = (cell..size.area())
+            .map(|cell| Vector::new(cell % size.x, cell / size.x))
+            .find(|&pos| !self.grid.contains(pos));
+        let Some(pos) = free else {
+            return false;
+        };
+        let next = pos.y * size.x + pos.x + 1;
 
-    fn shift_down(self) -> Self {
-        Self {
-            orientation: 0,
-            pos: Vector::new(0, self.pos.y + 1),
-            ..self
-        }
-    }
+        for (index, shape) in shapes.iter().enumerate() {
+            if self.remaining[index] == 0 {
+                continue;
+            }
+            for orientation in &shape.orientations {
+                let first = orientation.iter().min_by_key(|spot| (spot.y, spot.x));
+                let offset = pos - *first.expect("shapes have cells");
+                let placed = orientation.iter().map(|&spot| spot + offset);
+                let open = |spot: Vector| {
+                    spot.contained_in(Vector::zero(), size) && !self.grid.contains(spot)
+                };
+                if !placed.clone().all(open) {
+                    continue;
+                }
 
-    fn inc_index(&self, grid: HashMap<Vector, usize>) -> Self {
-        Self {
-            grid,
-            orientation: 0,
-            pos: Vector::zero(),
-            index: self.index + 1,
+                for spot in placed.clone() {
+                    self.grid.insert(spot, index);
+                }
+                self.remaining[index] -= 1;
+                trace::emit(|| {
+                    let label = format!("placed present {index}");
+                    self.frame(label).highlight(placed.clone())
+                });
+                if self.pack(shapes, next) {
+                    return true;
+                }
+                self.remaining[index] += 1;
+                for spot in placed {
+                    self.grid.remove(spot);
+                }
+            }
         }
-    }
 
-    fn inc_orientation(self) -> Self {
-        Self {
-            orientation: self.orientation + 1,
-            ..self
+        if self.spare == 0 {
+            return false;
         }
+        self.spare -= 1;
+        let fits = self.pack(shapes, next);
+        self.spare += 1;
+        fits
     }
-}
 
-impl PartialOrd for State {
-    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        Some(self.cmp(other))
-    }
-}
-
-impl Ord for State {
-    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        self.index.cmp(&other.index)
+    /// The region with each placed present drawn as its shape's index, in base 36.
+    fn frame(&self, label: impl Into<String>) -> Frame {
+        Frame::new(label)
+            .cells(self.grid.size(), |pos| match self.grid.get(pos) {
+                Some(&index) => char::from_digit((index % 36) as u32, 36).unwrap(),
+                None => '.',
+            })
+            .counter("placed", self.grid.len())
+            .counter("spare", self.spare)
     }
 }
 
@@ -225,26 +239,38 @@ impl Shape {
         );
 
         Self {
-            size,
             orientations: orientations.iter().cloned().collect(),
             count: set.len(),
         }
     }
 }
 
-fn pretty_print(grid: &HashMap<Vector, usize>, size: Vector) {
-    for y in 0..size.y {
-        let mut line = Vec::new();
-        for x in 0..size.x {
-            if let Some(n) = grid.get(&Vector::new(x, y)) {
-                line.push(n.to_string());
-            } else {
-                line.push(".".to_string());
-            }
-        }
-        println!("{}", line.join(""));
+utils::solution::test_solution!(aoc2025, day12);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use utils::solution::split;
+
+    fn error(input: &str) -> String {
+        Day::new(split(input.into())).err().unwrap().to_string()
     }
-    println!();
-}
 
-utils::solution::test_solution!(aoc2025, day12);
+    #[test]
+    fn malformed() {
+        assert_eq!(error(""), "line 1: missing shapes and regions");
+        assert_eq!(error("4x4: 1"), "line 1: missing shapes before the regions");
+        assert_eq!(
+            error("0:\n\n4x4: 1"),
+            "line 1: missing shape below the header"
+        );
+        assert_eq!(
+            error("###\n\n4x4: 1"),
+            "line 1, column 1: expected a header like `0:`"
+        );
+        assert_eq!(
+            error("0:\n###\n\n4x4: 1 2"),
+            "line 4: expected 1 counts, one per shape, found 2"
+        );
+    }
+}
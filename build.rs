@@ -0,0 +1,40 @@
+//! Registers every `aocYYYY_dayDD` dependency of the `aoc` binary as a solution.
+//!
+//! Generates `registry.rs` in `OUT_DIR`, listing each day's erased solution under its explicit year and day.
+
+use std::{env, fs, path::Path};
+
+fn main() {
+    println!("cargo::rerun-if-changed=Cargo.toml");
+
+    let manifest = fs::read_to_string("Cargo.toml").expect("failed to read Cargo.toml");
+    let manifest: toml::Table = toml::from_str(&manifest).expect("failed to parse Cargo.toml");
+    let dependencies = manifest
+        .get("dependencies")
+        .and_then(|dependencies| dependencies.as_table())
+        .expect("Cargo.toml has no dependencies");
+
+    let mut days = dependencies
+        .keys()
+        .filter_map(|name| {
+            let (year, day) = name.strip_prefix("aoc")?.split_once("_day")?;
+            Some((
+                year.parse::<usize>().ok()?,
+                day.parse::<usize>().ok()?,
+                name,
+            ))
+        })
+        .collect::<Vec<_>>();
+    days.sort();
+
+    let mut registry =
+        String::from("/// Every day crate in `Cargo.toml` as `(year, day, solution)`, sorted.\n");
+    registry += "pub const SOLUTIONS: &[(usize, usize, Solve)] = &[\n";
+    for (year, day, name) in days {
+        registry += &format!("    ({year}, {day}, erase::<{name}::Day, _, _, _>),\n");
+    }
+    registry += "];\n";
+
+    let out = env::var("OUT_DIR").expect("OUT_DIR is set by cargo");
+    fs::write(Path::new(&out).join("registry.rs"), registry).expect("failed to write registry");
+}
@@ -1,29 +0,0 @@
-use utils::solution::{Run, Solution};
-
-pub const DAYS: &[fn(bool) -> Run] = &[
-    aoc2023_day01::Day::run,
-    aoc2023_day02::Day::run,
-    aoc2023_day03::Day::run,
-    aoc2023_day04::Day::run,
-    aoc2023_day05::Day::run,
-    aoc2023_day06::Day::run,
-    aoc2023_day07::Day::run,
-    aoc2023_day08::Day::run,
-    // aoc2023_day09::Day::run,
-    // aoc2023_day10::Day::run,
-    // aoc2023_day11::Day::run,
-    // aoc2023_day12::Day::run,
-    // aoc2023_day13::Day::run,
-    // aoc2023_day14::Day::run,
-    // aoc2023_day15::Day::run,
-    // aoc2023_day16::Day::run,
-    // aoc2023_day17::Day::run,
-    // aoc2023_day18::Day::run,
-    // aoc2023_day19::Day::run,
-    // aoc2023_day20::Day::run,
-    aoc2023_day21::Day::run,
-    aoc2023_day22::Day::run,
-    aoc2023_day23::Day::run,
-    // aoc2023_day24::Day::run,
-    // aoc2023_day25::Day::run,
-];
@@ -1,29 +0,0 @@
-use utils::solution::{Run, Solution};
-
-pub const DAYS: &[fn(bool) -> Run] = &[
-    aoc2024_day01::Day::run,
-    // aoc2024_day02::Day::run,
-    // aoc2024_day03::Day::run,
-    // aoc2024_day04::Day::run,
-    // aoc2024_day05::Day::run,
-    // aoc2024_day06::Day::run,
-    // aoc2024_day07::Day::run,
-    // aoc2024_day08::Day::run,
-    // aoc2024_day09::Day::run,
-    // aoc2024_day10::Day::run,
-    // aoc2024_day11::Day::run,
-    // aoc2024_day12::Day::run,
-    // aoc2024_day13::Day::run,
-    // aoc2024_day14::Day::run,
-    // aoc2024_day15::Day::run,
-    // aoc2024_day16::Day::run,
-    // aoc2024_day17::Day::run,
-    // aoc2024_day18::Day::run,
-    // aoc2024_day19::Day::run,
-    // aoc2024_day20::Day::run,
-    // aoc2024_day21::Day::run,
-    // aoc2024_day22::Day::run,
-    // aoc2024_day23::Day::run,
-    // aoc2024_day24::Day::run,
-    // aoc2024_day25::Day::run,
-];
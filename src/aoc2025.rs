@@ -1,16 +0,0 @@
-use utils::solution::{Run, Solution};
-
-pub const DAYS: &[fn(bool) -> Run] = &[
-    aoc2025_day01::Day::run,
-    aoc2025_day02::Day::run,
-    aoc2025_day03::Day::run,
-    aoc2025_day04::Day::run,
-    aoc2025_day05::Day::run,
-    aoc2025_day06::Day::run,
-    aoc2025_day07::Day::run,
-    aoc2025_day08::Day::run,
-    aoc2025_day09::Day::run,
-    aoc2025_day10::Day::run,
-    aoc2025_day11::Day::run,
-    aoc2025_day12::Day::run,
-];
@@ -0,0 +1,209 @@
+use std::{collections::BTreeMap, fs, io, path::Path, time::Duration};
+
+use serde::{Deserialize, Serialize};
+
+use crate::bench::{Bench, Stats};
+
+/// Changes in median smaller than this are timer noise, never a regression.
+const NOISE: Duration = Duration::from_micros(1);
+
+/// Two-sided critical value of Welch's t-test at roughly 99% confidence.
+const CRITICAL_T: f64 = 2.58;
+
+/// The stored statistics of one benchmarked phase.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct Entry {
+    pub runs: usize,
+    pub median_ns: u128,
+    pub mean_ns: u128,
+    pub stddev_ns: u128,
+}
+
+impl From<&Stats> for Entry {
+    fn from(stats: &Stats) -> Self {
+        Self {
+            runs: stats.runs,
+            median_ns: stats.median.as_nanos(),
+            mean_ns: stats.mean.as_nanos(),
+            stddev_ns: stats.stddev.as_nanos(),
+        }
+    }
+}
+
+/// Benchmark results keyed by `year/day/part`, e.g. `2025/01/a`, where part is `parse`, `a` or `b`.
+pub type Baseline = BTreeMap<String, Entry>;
+
+fn entries(bench: &Bench) -> impl Iterator<Item = (String, Entry)> {
+    let (year, day) = (bench.report.year, bench.report.day);
+    [
+        ("parse", &bench.parse),
+        ("a", &bench.part_a),
+        ("b", &bench.part_b),
+    ]
+    .into_iter()
+    .filter(|(_, stats)| stats.runs > 0)
+    .map(move |(part, stats)| (format!("{year}/{day:0>2}/{part}"), Entry::from(stats)))
+}
+
+pub fn load(path: &Path) -> io::Result<Baseline> {
+    let text = fs::read_to_string(path)?;
+    serde_json::from_str(&text).map_err(io::Error::other)
+}
+
+/// Writes the benchmarks into the baseline at `path`, keeping entries for other days.
+pub fn save(path: &Path, benches: &[Bench]) -> io::Result<()> {
+    let mut baseline = match load(path) {
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Baseline::new(),
+        result => result?,
+    };
+    baseline.extend(benches.iter().flat_map(entries));
+    fs::write(path, serde_json::to_string_pretty(&baseline)? + "\n")
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verdict {
+    Regressed,
+    Improved,
+    Unchanged,
+    /// Nothing to compare against in the baseline.
+    New,
+}
+
+/// One phase compared against its baseline.
+#[derive(Debug, Clone)]
+pub struct Comparison {
+    pub key: String,
+    pub baseline: Option<Entry>,
+    pub current: Entry,
+    /// Relative change of the median, `0.1` being 10% slower.
+    pub change: f64,
+    pub verdict: Verdict,
+}
+
+/// Compares benchmarks against the baseline, flagging significant changes beyond `threshold`.
+pub fn compare(baseline: &Baseline, benches: &[Bench], threshold: f64) -> Vec<Comparison> {
+    benches
+        .iter()
+        .flat_map(entries)
+        .map(|(key, current)| {
+            let Some(&old) = baseline.get(&key) else {
+                return Comparison {
+                    key,
+                    baseline: None,
+                    current,
+                    change: 0.0,
+                    verdict: Verdict::New,
+                };
+            };
+
+            let change = current.median_ns as f64 / (old.median_ns as f64).max(1.0) - 1.0;
+            let noticeable = current.median_ns.abs_diff(old.median_ns) >= NOISE.as_nanos()
+                && change.abs() > threshold
+                && significant(&old, &current);
+            let verdict = match noticeable {
+                true if change > 0.0 => Verdict::Regressed,
+                true => Verdict::Improved,
+                false => Verdict::Unchanged,
+            };
+            Comparison {
+                key,
+                baseline: Some(old),
+                current,
+                change,
+                verdict,
+            }
+        })
+        .collect()
+}
+
+/// Whether the means differ according to Welch's t-test.
+fn significant(old: &Entry, new: &Entry) -> bool {
+    let variance = |entry: &Entry| (entry.stddev_ns as f64).powi(2) / entry.runs.max(1) as f64;
+    let error = (variance(old) + variance(new)).sqrt();
+    let difference = (new.mean_ns as f64 - old.mean_ns as f64).abs();
+    match error {
+        0.0 => difference > 0.0,
+        _ => difference / error > CRITICAL_T,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use utils::solution::Report;
+
+    /// A day whose part a took `mean` with `stddev` over 30 runs, its only benchmarked phase.
+    fn bench(mean: Duration, stddev: Duration) -> Bench {
+        let part_a = Stats {
+            runs: 30,
+            median: mean,
+            mean,
+            stddev,
+            ..Default::default()
+        };
+        Bench {
+            report: Report::new(2025, 1),
+            warmup: 1,
+            parse: Stats::default(),
+            part_a,
+            part_b: Stats::default(),
+            total: Stats::default(),
+        }
+    }
+
+    fn verdict(old: &Bench, new: &Bench) -> Verdict {
+        let baseline = entries(old).collect::<Baseline>();
+        let comparisons = compare(&baseline, std::slice::from_ref(new), 0.1);
+        assert_eq!(comparisons.len(), 1);
+        assert_eq!(comparisons[0].key, "2025/01/a");
+        comparisons[0].verdict
+    }
+
+    const MS: Duration = Duration::from_millis(1);
+    const STEADY: Duration = Duration::from_micros(100);
+
+    #[test]
+    fn regressed_and_improved() {
+        let old = bench(10 * MS, STEADY);
+        assert_eq!(verdict(&old, &bench(12 * MS, STEADY)), Verdict::Regressed);
+        assert_eq!(verdict(&old, &bench(8 * MS, STEADY)), Verdict::Improved);
+    }
+
+    #[test]
+    fn unchanged() {
+        let old = bench(10 * MS, STEADY);
+        // Significant, but within the threshold.
+        let within = bench(Duration::from_micros(10_500), STEADY);
+        assert_eq!(verdict(&old, &within), Verdict::Unchanged);
+        // Beyond the threshold, but too noisy to tell.
+        let noisy = bench(12 * MS, 10 * MS);
+        assert_eq!(
+            verdict(&bench(10 * MS, 10 * MS), &noisy),
+            Verdict::Unchanged
+        );
+        // Tripled, but by less than the timer's noise.
+        let tiny = bench(Duration::from_nanos(300), Duration::ZERO);
+        let old = bench(Duration::from_nanos(100), Duration::ZERO);
+        assert_eq!(verdict(&old, &tiny), Verdict::Unchanged);
+    }
+
+    #[test]
+    fn zero_variance() {
+        let exact = bench(10 * MS, Duration::ZERO);
+        assert_eq!(verdict(&exact, &exact), Verdict::Unchanged);
+        let slower = bench(12 * MS, Duration::ZERO);
+        assert_eq!(verdict(&exact, &slower), Verdict::Regressed);
+
+        let entry = Entry::from(&exact.part_a);
+        assert!(!significant(&entry, &entry));
+        let empty = Entry { runs: 0, ..entry };
+        assert!(!significant(&empty, &empty));
+    }
+
+    #[test]
+    fn new_phase() {
+        let comparisons = compare(&Baseline::new(), &[bench(MS, STEADY)], 0.1);
+        assert_eq!(comparisons[0].verdict, Verdict::New);
+        assert_eq!(comparisons[0].change, 0.0);
+    }
+}
@@ -0,0 +1,216 @@
+use std::time::{Duration, Instant};
+
+use utils::{
+    dynamic,
+    memory::Memory,
+    solution::{Failure, Options, Report},
+};
+
+use crate::select::Solve;
+
+/// How long and how often each day is benchmarked.
+#[derive(Debug, Clone, Copy)]
+pub struct Config {
+    /// Untimed runs before measuring, to warm caches and the allocator.
+    pub warmup: usize,
+    /// Measuring stops once this much time was spent, after at least `min_runs`.
+    pub budget: Duration,
+    pub min_runs: usize,
+    pub max_runs: usize,
+}
+
+/// Summary statistics over the measured runs of one phase.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Stats {
+    pub runs: usize,
+    pub min: Duration,
+    pub median: Duration,
+    pub p95: Duration,
+    pub max: Duration,
+    pub mean: Duration,
+    pub stddev: Duration,
+    /// Runs outside the Tukey fences, 1.5 interquartile ranges beyond the quartiles.
+    pub outliers: usize,
+}
+
+impl Stats {
+    pub fn new(mut times: Vec<Duration>) -> Self {
+        if times.is_empty() {
+            return Self::default();
+        }
+        times.sort();
+
+        let runs = times.len();
+        let mean = times.iter().sum::<Duration>() / runs as u32;
+        let variance = times
+            .iter()
+            .map(|time| (time.as_secs_f64() - mean.as_secs_f64()).powi(2))
+            .sum::<f64>()
+            / runs as f64;
+
+        let q1 = percentile(&times, 0.25).as_secs_f64();
+        let q3 = percentile(&times, 0.75).as_secs_f64();
+        let (low, high) = (q1 - 1.5 * (q3 - q1), q3 + 1.5 * (q3 - q1));
+        let outliers = times
+            .iter()
+            .filter(|time| !(low..=high).contains(&time.as_secs_f64()))
+            .count();
+
+        Self {
+            runs,
+            min: times[0],
+            median: median(&times),
+            p95: percentile(&times, 0.95),
+            max: times[runs - 1],
+            mean,
+            stddev: Duration::from_secs_f64(variance.sqrt()),
+            outliers,
+        }
+    }
+}
+
+fn median(sorted: &[Duration]) -> Duration {
+    let mid = sorted.len() / 2;
+    if sorted.len().is_multiple_of(2) {
+        (sorted[mid - 1] + sorted[mid]) / 2
+    } else {
+        sorted[mid]
+    }
+}
+
+/// The nearest-rank percentile of sorted, non-empty `sorted`.
+fn percentile(sorted: &[Duration], p: f64) -> Duration {
+    let rank = (p * sorted.len() as f64).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
+/// The benchmark of a single day, keeping the last report for its answers and heap usage.
+#[derive(Debug, Clone)]
+pub struct Bench {
+    pub report: Report,
+    pub warmup: usize,
+    pub parse: Stats,
+    pub part_a: Stats,
+    pub part_b: Stats,
+    pub total: Stats,
+}
+
+impl Bench {
+    /// Each phase's statistics and heap usage, including phases without runs.
+    pub fn phases(&self) -> [(&'static str, &Stats, Option<Memory>); 4] {
+        let report = &self.report;
+        [
+            ("parse", &self.parse, report.parse_memory),
+            ("part_a", &self.part_a, report.part_a.memory),
+            ("part_b", &self.part_b, report.part_b.memory),
+            ("total", &self.total, report.memory()),
+        ]
+    }
+}
+
+/// Benchmarks one day, or returns the report explaining why it couldn't run.
+pub fn run(solve: Solve, options: &Options, config: &Config) -> Result<Bench, Box<Report>> {
+    let attempt = || {
+        std::panic::catch_unwind(|| dynamic::run(solve, options))
+            .map_err(|_| Box::new(Report::failed(options.year, options.day, Failure::Panicked)))
+            .and_then(|report| {
+                let timed_out = report.parts().iter().any(|(_, part)| part.timed_out);
+                match report.failure.is_some() || timed_out {
+                    true => Err(Box::new(report)),
+                    false => Ok(report),
+                }
+            })
+    };
+
+    let mut report = attempt()?;
+    for _ in 1..config.warmup {
+        report = attempt()?;
+    }
+
+    let mut reports = vec![];
+    let start = Instant::now();
+    while reports.len() < config.max_runs
+        && (reports.len() < config.min_runs || start.elapsed() < config.budget)
+    {
+        reports.push(attempt()?);
+    }
+
+    // A part left out by `--part` has no runs.
+    let stats = |time: fn(&Report) -> Option<Duration>| {
+        Stats::new(reports.iter().filter_map(time).collect())
+    };
+    let bench = Bench {
+        warmup: config.warmup.max(1),
+        parse: stats(|report| report.parse),
+        part_a: stats(|report| report.part_a.time),
+        part_b: stats(|report| report.part_b.time),
+        total: stats(|report| {
+            let parts = report
+                .parts()
+                .map(|(_, part)| part.time.unwrap_or_default());
+            Some(report.parse? + parts.iter().sum::<Duration>())
+        }),
+        report: reports.pop().unwrap_or(report),
+    };
+    Ok(bench)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn millis(times: &[u64]) -> Vec<Duration> {
+        times.iter().copied().map(Duration::from_millis).collect()
+    }
+
+    #[test]
+    fn odd_runs() {
+        let stats = Stats::new(millis(&[5, 1, 3, 2, 4]));
+        assert_eq!(stats.runs, 5);
+        assert_eq!(stats.min, Duration::from_millis(1));
+        assert_eq!(stats.median, Duration::from_millis(3));
+        assert_eq!(stats.p95, Duration::from_millis(5));
+        assert_eq!(stats.max, Duration::from_millis(5));
+        assert_eq!(stats.mean, Duration::from_millis(3));
+        // The population variance is 2ms².
+        let stddev = stats.stddev.as_secs_f64() * 1000.0;
+        assert!((stddev - 2f64.sqrt()).abs() < 1e-6, "{stddev}");
+        assert_eq!(stats.outliers, 0);
+    }
+
+    #[test]
+    fn even_runs() {
+        let stats = Stats::new(millis(&[4, 1, 3, 2]));
+        assert_eq!(stats.median, Duration::from_micros(2500));
+        assert_eq!(stats.mean, Duration::from_micros(2500));
+    }
+
+    #[test]
+    fn outliers() {
+        // Quartiles 11 and 17, so the fences are at 2 and 26.
+        let stats = Stats::new(millis(&[1, 10, 11, 12, 13, 14, 15, 16, 17, 18, 100]));
+        assert_eq!(stats.outliers, 2);
+        assert_eq!(stats.median, Duration::from_millis(14));
+        assert_eq!(stats.p95, Duration::from_millis(100));
+
+        // Runs on the fences themselves are kept.
+        let stats = Stats::new(millis(&[2, 10, 11, 12, 13, 14, 15, 16, 17, 18, 26]));
+        assert_eq!(stats.outliers, 0);
+    }
+
+    #[test]
+    fn percentiles() {
+        let sorted = millis(&[1, 2, 3, 4, 5, 6, 7, 8, 9, 10]);
+        assert_eq!(percentile(&sorted, 0.0), Duration::from_millis(1));
+        assert_eq!(percentile(&sorted, 0.25), Duration::from_millis(3));
+        assert_eq!(percentile(&sorted, 0.95), Duration::from_millis(10));
+        assert_eq!(percentile(&sorted, 1.0), Duration::from_millis(10));
+    }
+
+    #[test]
+    fn no_runs() {
+        let stats = Stats::new(vec![]);
+        assert_eq!(stats.runs, 0);
+        assert_eq!(stats.median, Duration::ZERO);
+    }
+}
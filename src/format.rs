@@ -0,0 +1,554 @@
+use std::time::Duration;
+
+use clap::ValueEnum;
+use serde::Serialize;
+use utils::{
+    answers::Verification,
+    memory::Memory,
+    solution::{Check, Failure, Part, Report},
+};
+
+use crate::{
+    baseline::{Comparison, Verdict},
+    bench::Bench,
+};
+
+/// How results are written to stdout.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum Format {
+    /// Human-readable output
+    #[default]
+    Text,
+    /// One JSON object per line, per year/day/part
+    Json,
+    /// One CSV row per year/day/part, after a header
+    Csv,
+}
+
+/// A single year/day/part result, the unit of machine-readable output.
+#[derive(Debug, Serialize)]
+struct Record<'a> {
+    year: usize,
+    day: usize,
+    part: char,
+    answer: Option<&'a str>,
+    samples: &'static str,
+    /// `correct`, `wrong` or `unverified` against the recorded answer.
+    verification: &'static str,
+    expected: Option<&'a str>,
+    parse_ns: Option<u128>,
+    time_ns: Option<u128>,
+    /// Heap usage, when built with the `memory` feature.
+    parse_peak_bytes: Option<usize>,
+    parse_allocations: Option<usize>,
+    peak_bytes: Option<usize>,
+    allocations: Option<usize>,
+    /// Why the day failed, or which sample went wrong.
+    detail: Option<String>,
+}
+
+const CSV_HEADER: &str = "year,day,part,answer,samples,verification,expected,parse_ns,time_ns,\
+    parse_peak_bytes,parse_allocations,peak_bytes,allocations,detail";
+
+impl<'a> Record<'a> {
+    fn from_report(report: &'a Report) -> impl Iterator<Item = Record<'a>> {
+        let parts = report.parts().into_iter();
+        parts
+            .filter(|(_, result)| !result.skipped())
+            .map(|(part, result)| Record {
+                year: report.year,
+                day: report.day,
+                part,
+                answer: result.answer.as_deref(),
+                samples: result.samples.label(),
+                verification: match result.verification {
+                    Verification::Correct => "correct",
+                    Verification::Wrong { .. } => "wrong",
+                    Verification::Unverified => "unverified",
+                },
+                expected: match &result.verification {
+                    Verification::Wrong { expected } => Some(expected),
+                    _ => None,
+                },
+                parse_ns: report.parse.map(|parse| parse.as_nanos()),
+                time_ns: result.time.map(|time| time.as_nanos()),
+                parse_peak_bytes: report.parse_memory.map(|memory| memory.peak),
+                parse_allocations: report.parse_memory.map(|memory| memory.allocations),
+                peak_bytes: result.memory.map(|memory| memory.peak),
+                allocations: result.memory.map(|memory| memory.allocations),
+                detail: match &report.failure {
+                    Some(failure) => Some(failure.to_string()),
+                    None if result.timed_out => Some("timed out on the real input".to_string()),
+                    None => sample_detail(&result.samples),
+                },
+            })
+    }
+
+    fn csv(&self) -> String {
+        let optional = |value: Option<String>| value.unwrap_or_default();
+        [
+            self.year.to_string(),
+            self.day.to_string(),
+            self.part.to_string(),
+            csv_field(self.answer.unwrap_or_default()),
+            self.samples.to_string(),
+            self.verification.to_string(),
+            csv_field(self.expected.unwrap_or_default()),
+            optional(self.parse_ns.map(|ns| ns.to_string())),
+            optional(self.time_ns.map(|ns| ns.to_string())),
+            optional(self.parse_peak_bytes.map(|bytes| bytes.to_string())),
+            optional(self.parse_allocations.map(|count| count.to_string())),
+            optional(self.peak_bytes.map(|bytes| bytes.to_string())),
+            optional(self.allocations.map(|count| count.to_string())),
+            csv_field(self.detail.as_deref().unwrap_or_default()),
+        ]
+        .join(",")
+    }
+}
+
+/// Statistics for one phase of a benchmarked day, where `part` is `parse`, `a`, `b` or `total`.
+#[derive(Debug, Serialize)]
+struct BenchRecord<'a> {
+    year: usize,
+    day: usize,
+    part: &'static str,
+    answer: Option<&'a str>,
+    runs: usize,
+    warmup: usize,
+    min_ns: u128,
+    median_ns: u128,
+    p95_ns: u128,
+    max_ns: u128,
+    mean_ns: u128,
+    stddev_ns: u128,
+    outliers: usize,
+    /// Heap usage of the last run, when built with the `memory` feature.
+    peak_bytes: Option<usize>,
+    allocations: Option<usize>,
+}
+
+const BENCH_CSV_HEADER: &str = "year,day,part,answer,runs,warmup,min_ns,median_ns,p95_ns,max_ns,\
+    mean_ns,stddev_ns,outliers,peak_bytes,allocations";
+
+impl<'a> BenchRecord<'a> {
+    fn from_bench(bench: &'a Bench) -> impl Iterator<Item = BenchRecord<'a>> {
+        let report = &bench.report;
+        let answers = [
+            None,
+            report.part_a.answer.as_deref(),
+            report.part_b.answer.as_deref(),
+            None,
+        ];
+        let parts = ["parse", "a", "b", "total"];
+        let phases = bench.phases().into_iter().zip(parts).zip(answers);
+        phases.filter(|(((_, stats, _), _), _)| stats.runs > 0).map(
+            |(((_, stats, memory), part), answer)| BenchRecord {
+                year: report.year,
+                day: report.day,
+                part,
+                answer,
+                runs: stats.runs,
+                warmup: bench.warmup,
+                min_ns: stats.min.as_nanos(),
+                median_ns: stats.median.as_nanos(),
+                p95_ns: stats.p95.as_nanos(),
+                max_ns: stats.max.as_nanos(),
+                mean_ns: stats.mean.as_nanos(),
+                stddev_ns: stats.stddev.as_nanos(),
+                outliers: stats.outliers,
+                peak_bytes: memory.map(|memory| memory.peak),
+                allocations: memory.map(|memory| memory.allocations),
+            },
+        )
+    }
+
+    fn csv(&self) -> String {
+        let optional = |value: Option<usize>| value.map_or(String::new(), |n| n.to_string());
+        [
+            self.year.to_string(),
+            self.day.to_string(),
+            self.part.to_string(),
+            csv_field(self.answer.unwrap_or_default()),
+            self.runs.to_string(),
+            self.warmup.to_string(),
+            self.min_ns.to_string(),
+            self.median_ns.to_string(),
+            self.p95_ns.to_string(),
+            self.max_ns.to_string(),
+            self.mean_ns.to_string(),
+            self.stddev_ns.to_string(),
+            self.outliers.to_string(),
+            optional(self.peak_bytes),
+            optional(self.allocations),
+        ]
+        .join(",")
+    }
+}
+
+fn sample_detail(samples: &Check) -> Option<String> {
+    match samples {
+        Check::Failed {
+            name,
+            expected,
+            received,
+        } => Some(format!(
+            "sample `{name}`: expected {expected}, received {received}"
+        )),
+        Check::Invalid { name, error } => Some(format!("sample `{name}`: {error}")),
+        _ => None,
+    }
+}
+
+/// A byte count in binary units, e.g. `1.5 MiB`.
+fn bytes(bytes: usize) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+    let mut size = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    format!("{size:.1} {}", UNITS[unit])
+}
+
+fn memory_line(memory: Memory) -> String {
+    format!(
+        " ** mem:\t{} peak, {} allocations",
+        bytes(memory.peak),
+        memory.allocations
+    )
+}
+
+/// Quotes a CSV field when it contains a separator, quote or newline.
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+impl Format {
+    /// Printed once before any report, or before any benchmark when `bench` is set.
+    pub fn begin(self, bench: bool) {
+        match (self, bench) {
+            (Format::Csv, false) => println!("{CSV_HEADER}"),
+            (Format::Csv, true) => println!("{BENCH_CSV_HEADER}"),
+            _ => {}
+        }
+    }
+
+    /// Prints the outcome of running a day, after `banner` in text mode.
+    pub fn report(self, banner: &str, report: &Report) {
+        match self {
+            Format::Text => {
+                println!("{banner}");
+                print_text(report);
+            }
+            _ => self.records(report),
+        }
+    }
+
+    /// Prints a day's benchmark statistics, or why it couldn't be benchmarked.
+    pub fn bench(self, bench: &Result<Bench, Box<Report>>) {
+        let bench = match bench {
+            Ok(bench) => bench,
+            Err(report) if self == Format::Text => {
+                let banner = format!("======= {} Day {:0>2} =======", report.year, report.day);
+                return self.report(&banner, report);
+            }
+            // Keeps machine-readable output to one record shape.
+            Err(report) => {
+                let failure = match &report.failure {
+                    Some(failure) => failure.to_string(),
+                    None => "timed out".to_string(),
+                };
+                return eprintln!("{} day {:0>2}: {failure}", report.year, report.day);
+            }
+        };
+
+        match self {
+            Format::Text => print_bench(bench),
+            Format::Json => {
+                for record in BenchRecord::from_bench(bench) {
+                    println!("{}", serde_json::to_string(&record).unwrap());
+                }
+            }
+            Format::Csv => {
+                for record in BenchRecord::from_bench(bench) {
+                    println!("{}", record.csv());
+                }
+            }
+        }
+    }
+
+    /// Prints every benchmarked day, costliest first, in text mode only.
+    pub fn bench_summary(self, benches: &[Bench]) {
+        if self != Format::Text || benches.is_empty() {
+            return;
+        }
+
+        let mut benches = benches.iter().collect::<Vec<_>>();
+        benches.sort_by_key(|bench| std::cmp::Reverse(bench.total.median));
+        let total = benches
+            .iter()
+            .map(|bench| bench.total.median)
+            .sum::<Duration>();
+
+        println!("\n======= Summary (medians) =======");
+        println!(
+            "{:<8}{:>12}{:>12}{:>12}{:>12}{:>8}",
+            "day", "parse", "part_a", "part_b", "total", "share"
+        );
+        for bench in benches {
+            let share = bench.total.median.as_secs_f64() / total.as_secs_f64().max(f64::EPSILON);
+            println!(
+                "{:<8}{:>12.1?}{:>12.1?}{:>12.1?}{:>12.1?}{:>7.1}%",
+                format!("{}/{:0>2}", bench.report.year, bench.report.day),
+                bench.parse.median,
+                bench.part_a.median,
+                bench.part_b.median,
+                bench.total.median,
+                share * 100.0
+            );
+        }
+        println!("{:<8}{:>48.1?}", "all", total);
+    }
+
+    /// Prints the status and timing of every day that ran, in text mode only.
+    pub fn summary(self, reports: &[Report]) {
+        if self != Format::Text {
+            return;
+        }
+
+        let time =
+            |time: Option<Duration>| time.map_or("-".to_string(), |time| format!("{time:.1?}"));
+        let measured = reports.iter().any(|report| report.parse_memory.is_some());
+        let peak = |report: &Report| match measured {
+            true => format!(
+                "{:>12}",
+                report.memory().map_or("-".to_string(), |m| bytes(m.peak))
+            ),
+            false => String::new(),
+        };
+        println!("\n======= Summary =======");
+        println!(
+            "{:<8}{:>12}{:>12}{:>12}{}  {:<12}b",
+            "day",
+            "parse",
+            "part_a",
+            "part_b",
+            if measured {
+                format!("{:>12}", "peak")
+            } else {
+                String::new()
+            },
+            "a"
+        );
+        for report in reports {
+            let day = format!("{}/{:0>2}", report.year, report.day);
+            if let Some(failure) = &report.failure {
+                println!("{day:<8}  {failure}");
+                continue;
+            }
+            println!(
+                "{day:<8}{:>12}{:>12}{:>12}{}  {:<12}{}",
+                time(report.parse),
+                time(report.part_a.time),
+                time(report.part_b.time),
+                peak(report),
+                status(&report.part_a),
+                status(&report.part_b),
+            );
+        }
+    }
+
+    /// Prints benchmarks compared against a baseline, to stderr in machine-readable modes.
+    pub fn comparison(self, comparisons: &[Comparison]) {
+        let mut lines = vec![format!(
+            "{:<15}{:>12}{:>12}{:>10}  verdict",
+            "phase", "baseline", "current", "change"
+        )];
+        for comparison in comparisons {
+            let median = |ns: u128| format!("{:.1?}", Duration::from_nanos(ns as u64));
+            let verdict = match comparison.verdict {
+                Verdict::Regressed => "REGRESSED",
+                Verdict::Improved => "improved",
+                Verdict::Unchanged => "unchanged",
+                Verdict::New => "new",
+            };
+            lines.push(format!(
+                "{:<15}{:>12}{:>12}{:>+9.1}%  {verdict}",
+                comparison.key,
+                comparison
+                    .baseline
+                    .map_or("-".to_string(), |old| median(old.median_ns)),
+                median(comparison.current.median_ns),
+                comparison.change * 100.0,
+            ));
+        }
+
+        match self {
+            Format::Text => {
+                println!("\n======= Baseline comparison (medians) =======");
+                lines.iter().for_each(|line| println!("{line}"));
+            }
+            _ => lines.iter().for_each(|line| eprintln!("{line}")),
+        }
+    }
+
+    fn records(self, report: &Report) {
+        for record in Record::from_report(report) {
+            match self {
+                Format::Json => println!("{}", serde_json::to_string(&record).unwrap()),
+                Format::Csv => println!("{}", record.csv()),
+                Format::Text => unreachable!(),
+            }
+        }
+    }
+}
+
+fn print_bench(bench: &Bench) {
+    let report = &bench.report;
+    let measured = report.memory().is_some();
+    println!("======= {} Day {:0>2} =======", report.year, report.day);
+    println!("({} runs after {} warm-up)", bench.total.runs, bench.warmup);
+    println!(
+        "{:8}{:>12}{:>12}{:>12}{:>12}{:>12}{:>10}{}",
+        "",
+        "min",
+        "median",
+        "p95",
+        "max",
+        "stddev",
+        "outliers",
+        if measured {
+            format!("{:>12}{:>10}", "peak", "allocs")
+        } else {
+            String::new()
+        }
+    );
+    let phases = bench.phases().into_iter();
+    for (phase, stats, memory) in phases.filter(|(_, stats, _)| stats.runs > 0) {
+        let memory = memory.map_or(String::new(), |memory| {
+            format!("{:>12}{:>10}", bytes(memory.peak), memory.allocations)
+        });
+        println!(
+            "{:8}{:>12.1?}{:>12.1?}{:>12.1?}{:>12.1?}{:>12.1?}{:>10}{memory}",
+            format!("{phase}:"),
+            stats.min,
+            stats.median,
+            stats.p95,
+            stats.max,
+            stats.stddev,
+            stats.outliers
+        );
+    }
+}
+
+/// A one-word summary of a part, from its samples and verification.
+fn status(part: &Part) -> &'static str {
+    match (&part.samples, &part.answer, &part.verification) {
+        _ if part.skipped() => "-",
+        (Check::Passed, None, _) if !part.sample_runs.is_empty() => "samples ok",
+        (Check::Failed { .. }, _, _) => "sample fail",
+        (Check::Invalid { .. }, _, _) => "bad sample",
+        (Check::TimedOut, _, _) => "timeout",
+        (_, None, _) if part.timed_out => "timeout",
+        (_, None, _) => "unsolved",
+        (_, _, Verification::Correct) => "correct",
+        (_, _, Verification::Wrong { .. }) => "WRONG",
+        (_, _, Verification::Unverified) => "unverified",
+    }
+}
+
+fn print_text(report: &Report) {
+    match &report.failure {
+        Some(Failure::Input(err)) => return println!("input: failed to load! ({err})"),
+        Some(Failure::Parse(err)) => return println!("parse : failed! ({err})"),
+        Some(Failure::Panicked) => return println!("day {:0>2}: panicked!", report.day),
+        Some(Failure::TimedOut(limit)) => {
+            return println!("parse : timed out after {limit:?}!");
+        }
+        None => {}
+    }
+
+    // Only the samples ran when the real input wasn't parsed.
+    if let Some(parse) = report.parse {
+        println!("parse :\t{parse:?}");
+        if let Some(memory) = report.parse_memory {
+            println!("{}", memory_line(memory));
+        }
+        println!();
+    }
+    for (name, part) in [("part_a", &report.part_a), ("part_b", &report.part_b)] {
+        print_part(name, part);
+    }
+}
+
+fn print_part(name: &str, part: &Part) {
+    if part.skipped() {
+        return;
+    }
+    if !part.sample_runs.is_empty() {
+        return print_samples(name, part);
+    }
+
+    match &part.samples {
+        Check::Passed | Check::Skipped => match &part.answer {
+            Some(answer) => {
+                let verification = match &part.verification {
+                    Verification::Correct => "correct".to_string(),
+                    Verification::Wrong { expected } => format!("WRONG (expected {expected})"),
+                    Verification::Unverified => "unverified".to_string(),
+                };
+                println!("{name}:\t{answer}  [{verification}]");
+                println!(" ** in:\t{:?}", part.time.unwrap_or_default());
+                if let Some(memory) = part.memory {
+                    println!("{}", memory_line(memory));
+                }
+            }
+            None if part.timed_out => println!("{name}: timed out!"),
+            None => println!("{name}: unsolved!"),
+        },
+        Check::Failed {
+            name: sample,
+            expected,
+            received,
+        } => {
+            println!("{name}: failed! ({sample})");
+            println!("\texpected: {expected}");
+            println!("\treceived: {received}");
+        }
+        Check::Unsolved => println!("{name}: unsolved!"),
+        Check::TimedOut => println!("{name}: samples timed out!"),
+        Check::Invalid {
+            name: sample,
+            error,
+        } => {
+            println!("{name}: invalid sample! ({sample})");
+            println!("\t{error}");
+        }
+    }
+}
+
+fn print_samples(name: &str, part: &Part) {
+    let runs = &part.sample_runs;
+    let passed = runs.iter().filter(|run| run.passed()).count();
+    match passed == runs.len() {
+        true => println!("{name}: samples passed ({passed}/{})", runs.len()),
+        false => println!("{name}: samples failed! ({passed}/{} passed)", runs.len()),
+    }
+    for run in runs {
+        let outcome = match &run.received {
+            Ok(Some(received)) if run.passed() => format!("{received}  [ok]"),
+            Ok(Some(received)) => format!("expected {}, received {received}", run.expected),
+            Ok(None) => "unsolved!".to_string(),
+            Err(error) => format!("invalid! ({error})"),
+        };
+        println!("  `{}`:\t{outcome}  in {:.1?}", run.name, run.time);
+    }
+}
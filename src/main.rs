@@ -1,56 +1,337 @@
-use clap::Parser;
+use std::{
+    collections::BTreeMap,
+    fs::File,
+    io::{BufWriter, Read, Write},
+    path::{Path, PathBuf},
+    time::Duration,
+};
 
-mod aoc2023;
-mod aoc2024;
-mod aoc2025;
+use clap::{Parser, Subcommand};
+use format::Format;
+use select::{Days, Years};
+use utils::{
+    answers::{Answers, Conflict},
+    dynamic,
+    input::Input,
+    solution::{Check, Failure, Mode, Options, Part, Report},
+};
+
+mod baseline;
+mod bench;
+mod format;
+mod pool;
+mod render;
+mod scaffold;
+mod select;
+mod watch;
+
+#[cfg(feature = "memory")]
+#[global_allocator]
+static ALLOCATOR: utils::memory::Counting = utils::memory::Counting;
 
 #[derive(Parser, Debug)]
 struct Args {
-    /// Which year to select
-    #[arg(short, long, default_value_t = 2025)]
-    year: usize,
-    /// Which day to run
-    #[arg(short, long)]
-    day: Option<usize>,
-    /// Runs all days for the selected year
+    #[command(subcommand)]
+    command: Option<Command>,
+    /// Which years to select: `all`, or a list such as `2023,2025`
+    #[arg(short, long, default_value = "2025")]
+    year: Years,
+    /// Which days to run, a list of days and ranges such as `1..=5,9`
+    #[arg(short, long, conflicts_with = "all")]
+    day: Option<Days>,
+    /// Runs all days for the selected years
     #[arg(short, long)]
     all: bool,
-    /// Benchmarks the selected day's solution
+    /// Benchmarks the selected days' solutions
     #[arg(short, long)]
     bench: bool,
-    /// How many iterations to average over in the benchmark
-    #[arg(short, long, default_value_t = 10)]
+    /// The most measured runs per day in the benchmark
+    #[arg(short, long, default_value_t = 1000)]
     count: usize,
+    /// Untimed runs per day before the benchmark starts measuring
+    #[arg(short, long, default_value_t = 3)]
+    warmup: usize,
+    /// Milliseconds per day after which the benchmark stops measuring
+    #[arg(long, default_value_t = 1000)]
+    budget: u64,
+    /// Saves the benchmark results into this baseline file, keeping other days' results
+    #[arg(long, requires = "bench")]
+    save_baseline: Option<PathBuf>,
+    /// Compares the benchmark against this baseline file, failing on regressions
+    #[arg(long, requires = "bench")]
+    compare: Option<PathBuf>,
+    /// Percentage change in median that counts as a regression or improvement
+    #[arg(long, default_value_t = 10.0)]
+    threshold: f64,
+    /// Reads the selected day's input from this file instead, or from stdin if `-`
+    #[arg(short, long)]
+    input: Option<PathBuf>,
+    /// Directory of inputs laid out as `<year>/day<NN>.txt`, used before the embedded inputs
+    #[arg(long, default_value = "inputs")]
+    inputs: PathBuf,
+    /// How to print results
+    #[arg(long, value_enum, default_value_t)]
+    format: Format,
+    /// How many days to run at once, or 0 for one per CPU; benchmarks always run one at a time
+    #[arg(short, long, default_value_t = 1)]
+    jobs: usize,
+    /// Records the unverified real answers whose samples passed in the year's `answers.toml`
+    #[arg(long, conflicts_with = "bench")]
+    record: bool,
+    /// Lets `--record` replace answers recorded differently, or for another input
+    #[arg(long, requires = "record")]
+    force: bool,
+    /// Rebuilds and reruns the selected days when their sources or inputs change, under `cargo run`
+    #[arg(long, conflicts_with_all = ["bench", "record", "format"])]
+    watch: bool,
+    /// Plays the frames the solutions emit as an animation in the terminal
+    #[arg(long, conflicts_with_all = ["bench", "format", "watch"])]
+    animate: bool,
+    /// Writes the frames the solutions emit to this file as plain text
+    #[arg(long, conflicts_with = "bench")]
+    frames: Option<PathBuf>,
+    /// Only solves the samples, showing each one's expected and received answer
+    #[arg(long, conflicts_with_all = ["real", "bench", "record"])]
+    sample: bool,
+    /// Solves the real input even if the samples fail, without running them
+    #[arg(long)]
+    real: bool,
+    /// Runs only part `a` or `b`
+    #[arg(short, long, value_parser = parse_part)]
+    part: Option<char>,
+    /// Seconds each phase of a day may take before it's reported as timed out, leaving it running
+    #[arg(long)]
+    timeout: Option<f64>,
+    /// Milliseconds between the frames of `--animate`
+    #[arg(long, default_value_t = 50)]
+    delay: u64,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Generates a day's crate from `template/` and registers it
+    New(scaffold::NewDay),
 }
 
 fn main() {
     let args = Args::parse();
-    let years = &[aoc2023::DAYS, aoc2024::DAYS, aoc2025::DAYS];
-    let days = years[args.year - 2023];
-
-    if args.all {
-        for (index, day) in days.iter().enumerate() {
-            println!("\n======= {} Day {:0>2} =======", args.year, index + 1);
-            day(false);
+    if let Some(Command::New(new)) = &args.command {
+        let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+        if let Err(err) = scaffold::new_day(root, new) {
+            fail(&format!("failed to create day: {err}"));
         }
         return;
     }
+    let selection =
+        select::resolve(&args.year, args.day.as_ref(), args.all).unwrap_or_else(|err| fail(&err));
+    for &(year, day) in &selection.missing {
+        let implemented = select::implemented(year);
+        eprintln!("{year} day {day:0>2} is missing, implemented days are {implemented}");
+    }
+    let selected = selection.solutions;
+    if selected.is_empty() {
+        fail("none of the selected days are implemented");
+    }
+    if args.input.is_some() && selected.len() > 1 {
+        fail("`--input` needs a single day to be selected");
+    }
 
-    let day = args.day.unwrap_or(days.len());
+    let stdin = args
+        .input
+        .as_ref()
+        .is_some_and(|path| path.as_os_str() == "-");
+    if args.watch && stdin {
+        fail("`--watch` can't reread stdin, pass `--input` a file instead");
+    }
+
+    let input = match &args.input {
+        Some(path) if path.as_os_str() == "-" => {
+            let mut text = String::new();
+            if let Err(err) = std::io::stdin().read_to_string(&mut text) {
+                fail(&format!("failed to read stdin: {err}"));
+            }
+            Input::Text(text)
+        }
+        Some(path) => Input::File(path.clone()),
+        None => Input::Directory(args.inputs.clone()),
+    };
+    let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+    if args.watch {
+        let days = selected.iter().map(|&(year, day, _)| (year, day));
+        watch::watch(root, &days.collect::<Vec<_>>(), &input);
+    }
+
+    let mut answers = BTreeMap::new();
+    for &(year, _, _) in &selected {
+        answers.entry(year).or_insert_with(|| {
+            let path = Answers::path_in(root, year);
+            Answers::load(&path)
+                .unwrap_or_else(|err| fail(&format!("failed to read {}: {err}", path.display())))
+        });
+    }
+    let mode = match (args.sample, args.real) {
+        (true, _) => Mode::Samples,
+        (_, true) => Mode::Real,
+        _ => Mode::Checked,
+    };
+    let options = |year: usize, day: usize, bench: bool| Options {
+        year,
+        day,
+        input: input.clone(),
+        mode: if bench { Mode::Real } else { mode },
+        part: args.part,
+        answers: answers[&year].day(day),
+        timeout: args.timeout.map(Duration::from_secs_f64),
+        trace: !bench && (args.animate || args.frames.is_some()),
+    };
+
+    args.format.begin(args.bench);
     if args.bench {
-        let count = args.count as u32;
-        let times: Vec<_> = (0..count).map(|_| days[day - 1](true).unwrap()).collect();
-        let parse = times.iter().map(|(p, _, _)| p).sum::<std::time::Duration>() / count;
-        let a = times.iter().map(|(_, a, _)| a).sum::<std::time::Duration>() / count;
-        let b = times.iter().map(|(_, _, b)| b).sum::<std::time::Duration>() / count;
-        println!("======= {} Day {day:0>2} =======", args.year);
-        println!("(average over {count} runs)");
-        println!("parse :\t{parse:?}");
-        println!("part_a:\t{a:?}");
-        println!("part_b:\t{b:?}");
+        let config = bench::Config {
+            warmup: args.warmup,
+            budget: Duration::from_millis(args.budget),
+            min_runs: 3,
+            max_runs: args.count,
+        };
+        let mut benches = vec![];
+        for &(year, day, solve) in &selected {
+            let bench = bench::run(solve, &options(year, day, true), &config);
+            args.format.bench(&bench);
+            benches.extend(bench);
+        }
+        if selected.len() > 1 {
+            args.format.bench_summary(&benches);
+        }
+
+        let mut regressed = false;
+        if let Some(path) = &args.compare {
+            let baseline = baseline::load(path).unwrap_or_else(|err| {
+                fail(&format!(
+                    "failed to read baseline {}: {err}",
+                    path.display()
+                ))
+            });
+            let comparisons = baseline::compare(&baseline, &benches, args.threshold / 100.0);
+            args.format.comparison(&comparisons);
+            regressed = comparisons
+                .iter()
+                .any(|comparison| comparison.verdict == baseline::Verdict::Regressed);
+        }
+        if let Some(path) = &args.save_baseline
+            && let Err(err) = baseline::save(path, &benches)
+        {
+            fail(&format!(
+                "failed to save baseline {}: {err}",
+                path.display()
+            ));
+        }
+        if regressed {
+            std::process::exit(1);
+        }
         return;
     }
 
-    println!("===== {} Day {day:0>2} =====", args.year);
-    days[day - 1](false);
+    let jobs = match args.jobs {
+        0 => std::thread::available_parallelism().map_or(1, |jobs| jobs.get()),
+        jobs => jobs,
+    };
+    let mut frames = args.frames.as_ref().map(|path| {
+        let file = File::create(path)
+            .unwrap_or_else(|err| fail(&format!("failed to create {}: {err}", path.display())));
+        (path, BufWriter::new(file))
+    });
+    let mut reports = vec![];
+    let run = |&(year, day, solve): &(usize, usize, select::Solve)| {
+        let options = options(year, day, false);
+        std::panic::catch_unwind(|| dynamic::run(solve, &options))
+            .unwrap_or_else(|_| Report::failed(year, day, Failure::Panicked))
+    };
+    pool::run_ordered(&selected, jobs, run, |report| {
+        if args.animate
+            && let Err(err) = render::animate(&report, Duration::from_millis(args.delay))
+        {
+            fail(&format!("failed to animate: {err}"));
+        }
+        if let Some((path, file)) = &mut frames
+            && let Err(err) = render::dump(file, &report)
+        {
+            fail(&format!("failed to write {}: {err}", path.display()));
+        }
+        let banner = match selected.len() {
+            1 => format!("===== {} Day {:0>2} =====", report.year, report.day),
+            _ => format!("\n======= {} Day {:0>2} =======", report.year, report.day),
+        };
+        args.format.report(&banner, &report);
+        reports.push(report);
+    });
+    if selected.len() > 1 {
+        args.format.summary(&reports);
+    }
+    if let Some((path, mut file)) = frames
+        && let Err(err) = file.flush()
+    {
+        fail(&format!("failed to write {}: {err}", path.display()));
+    }
+
+    if args.record {
+        let mut refused = false;
+        for report in reports {
+            let Some(input) = report.input else {
+                continue;
+            };
+            // Only answers the samples back up, `record` won't change confirmed ones unless forced.
+            let answer = |part: Part| match part.samples {
+                Check::Passed => part.answer,
+                _ => None,
+            };
+            let (a, b) = (answer(report.part_a), answer(report.part_b));
+            if a.is_none() && b.is_none() {
+                continue;
+            }
+            let answers = answers.get_mut(&report.year).unwrap();
+            let Err(conflicts) = answers.record(report.day, input, a, b, args.force) else {
+                continue;
+            };
+            refused = true;
+            for conflict in conflicts {
+                let day = format!("{} day {:0>2}", report.year, report.day);
+                match conflict {
+                    Conflict::Input => eprintln!(
+                        "{day}: not recorded, its answers are for another input; \
+                         pass --force to replace them"
+                    ),
+                    Conflict::Answer {
+                        part,
+                        recorded,
+                        answer,
+                    } => eprintln!(
+                        "{day}: not recorded, part {part} is recorded as {recorded} \
+                         rather than {answer}; pass --force to replace it"
+                    ),
+                }
+            }
+        }
+        for (year, answers) in &answers {
+            let path = Answers::path_in(root, *year);
+            if let Err(err) = answers.save(&path) {
+                fail(&format!("failed to save {}: {err}", path.display()));
+            }
+        }
+        if refused {
+            std::process::exit(1);
+        }
+    }
+}
+
+fn parse_part(text: &str) -> Result<char, String> {
+    match text {
+        "a" => Ok('a'),
+        "b" => Ok('b'),
+        _ => Err(format!("there is no part `{text}`, try `a` or `b`")),
+    }
+}
+
+fn fail(message: &str) -> ! {
+    eprintln!("{message}");
+    std::process::exit(1);
 }
@@ -0,0 +1,101 @@
+use std::{
+    collections::BTreeMap,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc,
+    },
+    thread,
+};
+
+/// Matches the main thread, as some solutions recurse deeply.
+const STACK_SIZE: usize = 8 * 1024 * 1024;
+
+/// Runs `task` on every item using up to `jobs` threads, handing the results to `emit` in order.
+///
+/// Results are held back until all earlier items finished, so output never interleaves.
+pub fn run_ordered<I, R>(
+    items: &[I],
+    jobs: usize,
+    task: impl Fn(&I) -> R + Sync,
+    mut emit: impl FnMut(R),
+) where
+    I: Sync,
+    R: Send,
+{
+    if jobs <= 1 || items.len() <= 1 {
+        items.iter().for_each(|item| emit(task(item)));
+        return;
+    }
+
+    let next = AtomicUsize::new(0);
+    let (sender, receiver) = mpsc::channel();
+    thread::scope(|scope| {
+        for _ in 0..jobs.min(items.len()) {
+            let sender = sender.clone();
+            let (next, task) = (&next, &task);
+            thread::Builder::new()
+                .stack_size(STACK_SIZE)
+                .spawn_scoped(scope, move || {
+                    loop {
+                        let index = next.fetch_add(1, Ordering::Relaxed);
+                        let Some(item) = items.get(index) else {
+                            break;
+                        };
+                        if sender.send((index, task(item))).is_err() {
+                            break;
+                        }
+                    }
+                })
+                .expect("failed to spawn worker thread");
+        }
+        drop(sender);
+
+        let mut pending = BTreeMap::new();
+        let mut emitted = 0;
+        for (index, result) in receiver {
+            pending.insert(index, result);
+            while let Some(result) = pending.remove(&emitted) {
+                emit(result);
+                emitted += 1;
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod test {
+    use std::{sync::Mutex, time::Duration};
+
+    use super::*;
+
+    #[test]
+    fn in_order_when_finished_out_of_order() {
+        let items = [0, 1, 2, 3];
+        let finished = Mutex::new(vec![]);
+        let task = |&item: &usize| {
+            // The first item holds its worker until all the others are done.
+            while item == 0 && finished.lock().unwrap().len() < items.len() - 1 {
+                thread::sleep(Duration::from_millis(1));
+            }
+            finished.lock().unwrap().push(item);
+            item * 10
+        };
+        let mut emitted = vec![];
+        run_ordered(&items, items.len(), task, |result| emitted.push(result));
+        assert_eq!(finished.into_inner().unwrap().last(), Some(&0));
+        assert_eq!(emitted, [0, 10, 20, 30]);
+    }
+
+    #[test]
+    fn more_jobs_than_items() {
+        let items = [3, 1, 2];
+        let mut emitted = vec![];
+        run_ordered(&items, 16, |&item| item * 2, |result| emitted.push(result));
+        assert_eq!(emitted, [6, 2, 4]);
+
+        let mut emitted = vec![];
+        run_ordered(&[7], 4, |&item| item, |result| emitted.push(result));
+        assert_eq!(emitted, [7]);
+        run_ordered(&[] as &[usize], 4, |&item| item, |_| panic!("no items"));
+    }
+}
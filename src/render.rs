@@ -0,0 +1,84 @@
+use std::{
+    collections::HashSet,
+    io::{self, Write},
+    thread,
+    time::Duration,
+};
+
+use utils::{solution::Report, trace::Frame, vector::Vector};
+
+const CLEAR: &str = "\x1b[2J\x1b[H";
+const HIGHLIGHT: &str = "\x1b[1;33m";
+const RESET: &str = "\x1b[0m";
+
+/// Plays the day's frames in the terminal, redrawing every `delay`.
+pub fn animate(report: &Report, delay: Duration) -> io::Result<()> {
+    let mut out = io::stdout().lock();
+    for (phase, frames) in &report.trace {
+        for (index, frame) in frames.frames.iter().enumerate() {
+            write!(out, "{CLEAR}")?;
+            let heading = heading(report, phase, index, frames.frames.len(), frame);
+            draw(&mut out, &heading, frame, |cell| {
+                format!("{HIGHLIGHT}{cell}{RESET}")
+            })?;
+            out.flush()?;
+            thread::sleep(delay);
+        }
+        if frames.dropped > 0 {
+            writeln!(out, "({} more frames dropped)", frames.dropped)?;
+        }
+    }
+    Ok(())
+}
+
+/// Writes the day's frames as plain text, with highlighted cells drawn as `O`.
+pub fn dump(out: &mut impl Write, report: &Report) -> io::Result<()> {
+    for (phase, frames) in &report.trace {
+        for (index, frame) in frames.frames.iter().enumerate() {
+            let heading = heading(report, phase, index, frames.frames.len(), frame);
+            draw(out, &format!("=== {heading}"), frame, |_| "O".to_string())?;
+            writeln!(out)?;
+        }
+        if frames.dropped > 0 {
+            writeln!(out, "=== {} more frames dropped\n", frames.dropped)?;
+        }
+    }
+    Ok(())
+}
+
+fn heading(report: &Report, phase: &str, index: usize, total: usize, frame: &Frame) -> String {
+    format!(
+        "{}/{:0>2} {phase} [{}/{total}] {}",
+        report.year,
+        report.day,
+        index + 1,
+        frame.label
+    )
+}
+
+fn draw(
+    out: &mut impl Write,
+    heading: &str,
+    frame: &Frame,
+    highlight: impl Fn(char) -> String,
+) -> io::Result<()> {
+    writeln!(out, "{heading}")?;
+    if !frame.counters.is_empty() {
+        let counters = frame
+            .counters
+            .iter()
+            .map(|(name, value)| format!("{name} {value}"));
+        writeln!(out, "{}", counters.collect::<Vec<_>>().join("  "))?;
+    }
+    let highlights = frame.highlights.iter().copied().collect::<HashSet<_>>();
+    for (y, row) in frame.rows.iter().enumerate() {
+        let row = row.chars().enumerate().map(|(x, cell)| {
+            match highlights.contains(&(frame.origin + Vector::new_usize(x, y))) {
+                true => highlight(cell),
+                false => cell.to_string(),
+            }
+        });
+        writeln!(out, "{}", row.collect::<String>())?;
+    }
+    Ok(())
+}
@@ -0,0 +1,300 @@
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+/// A day to generate from `template/`.
+#[derive(Debug, Clone, clap::Args)]
+pub struct NewDay {
+    pub year: usize,
+    pub day: usize,
+    /// The answer type of part a, e.g. `i64` or `String`
+    #[arg(long, default_value = "i64")]
+    pub a: String,
+    /// The answer type of part b
+    #[arg(long, default_value = "i64")]
+    pub b: String,
+    /// A file to use as the sample input
+    #[arg(long)]
+    pub sample: Option<PathBuf>,
+}
+
+impl NewDay {
+    fn name(&self) -> String {
+        format!("aoc{}_day{:0>2}", self.year, self.day)
+    }
+
+    fn path(&self) -> String {
+        format!("aoc{}/day{:0>2}", self.year, self.day)
+    }
+}
+
+/// Generates the day's crate and adds it to the workspace, where `build.rs` registers it.
+pub fn new_day(root: &Path, new: &NewDay) -> io::Result<()> {
+    if !(1..=25).contains(&new.day) {
+        return Err(io::Error::other(format!("there is no day {}", new.day)));
+    }
+    let dir = root.join(new.path());
+    if dir.exists() {
+        let message = format!("{} already exists", dir.display());
+        return Err(io::Error::new(io::ErrorKind::AlreadyExists, message));
+    }
+
+    let template = root.join("template");
+    let manifest = fs::read_to_string(template.join("Cargo.toml"))?;
+    let lib = fs::read_to_string(template.join("src/lib.rs"))?;
+    let sample = match &new.sample {
+        Some(path) => fs::read_to_string(path)?,
+        None => fs::read_to_string(template.join("src/input_sample.txt"))?,
+    };
+
+    let manifest = replace(&manifest, "aoc2025_day00", &new.name())?;
+    let lib = generate_lib(&lib, new)?;
+    fs::create_dir_all(dir.join("src"))?;
+    fs::write(dir.join("Cargo.toml"), manifest)?;
+    fs::write(dir.join("src/lib.rs"), lib)?;
+    fs::copy(template.join("src/input.txt"), dir.join("src/input.txt"))?;
+    fs::write(dir.join("src/input_sample.txt"), sample)?;
+    println!("created {}", new.path());
+
+    edit(&root.join("Cargo.toml"), |text| {
+        register_dependency(text, new)
+    })?;
+    println!("registered {} in Cargo.toml", new.name());
+    Ok(())
+}
+
+fn generate_lib(lib: &str, new: &NewDay) -> io::Result<String> {
+    let (a, b) = (&new.a, &new.b);
+    let lib = replace(lib, "<i64, i64>", &format!("<{a}, {b}>"))?;
+    let lib = replace(
+        &lib,
+        "part_a(&self) -> Option<i64>",
+        &format!("part_a(&self) -> Option<{a}>"),
+    )?;
+    let lib = replace(
+        &lib,
+        "part_b(&self) -> Option<i64>",
+        &format!("part_b(&self) -> Option<{b}>"),
+    )?;
+
+    let sample = |part: &str, answer: &str| {
+        format!(
+            "{part}: vec![Sample::new(\"sample\", include_str!(\"input_sample.txt\"), {answer})]"
+        )
+    };
+    let lib = replace(
+        &lib,
+        &sample("samples_a", "0"),
+        &sample("samples_a", placeholder(a)),
+    )?;
+    let lib = replace(
+        &lib,
+        &sample("samples_b", "0"),
+        &sample("samples_b", placeholder(b)),
+    )?;
+
+    let day = format!("test_solution!(aoc{}, day{:0>2})", new.year, new.day);
+    replace(&lib, "test_solution!(aoc2025, day00)", &day)
+}
+
+/// A sample answer that compiles for the answer type until the real one is filled in.
+fn placeholder(ty: &str) -> &'static str {
+    const INTEGERS: &[&str] = &[
+        "i8", "i16", "i32", "i64", "i128", "isize", "u8", "u16", "u32", "u64", "u128", "usize",
+    ];
+    match INTEGERS.contains(&ty) {
+        true => "0",
+        false => "Default::default()",
+    }
+}
+
+/// Replaces every `from`, failing if the template no longer contains it.
+fn replace(text: &str, from: &str, to: &str) -> io::Result<String> {
+    match text.contains(from) {
+        true => Ok(text.replace(from, to)),
+        false => Err(io::Error::other(format!(
+            "template no longer contains `{from}`"
+        ))),
+    }
+}
+
+fn edit(path: &Path, change: impl FnOnce(&str) -> String) -> io::Result<()> {
+    let text = fs::read_to_string(path)?;
+    fs::write(path, change(&text))
+}
+
+/// Adds the crate as a dependency, uncommenting it if listed, along with its year's workspace members.
+fn register_dependency(text: &str, new: &NewDay) -> String {
+    let dependency = format!("{} = {{ path = \"{}\" }}", new.name(), new.path());
+    let mut lines = text.lines().map(str::to_string).collect::<Vec<_>>();
+
+    let members = format!("\t\"aoc{}/*\",", new.year);
+    if !lines.iter().any(|line| line.trim() == members.trim())
+        && let Some(start) = lines
+            .iter()
+            .position(|line| line.starts_with("members = ["))
+        && let Some(end) = lines[start..].iter().position(|line| line.trim() == "]")
+    {
+        lines.insert(start + end, members);
+    }
+
+    let commented = format!("# {dependency}");
+    if let Some(line) = lines.iter_mut().find(|line| line.trim() == commented) {
+        *line = dependency;
+    } else if !lines.iter().any(|line| line.trim() == dependency) {
+        let name = new.name();
+        let name = name.as_str();
+        let year = format!("aoc{}_day", new.year);
+        let of_year = |line: &String| day_name(line).is_some_and(|day| day.starts_with(&year));
+        match lines.iter().rposition(of_year) {
+            Some(last) => {
+                let later = |line: &String| of_year(line) && day_name(line) > Some(name);
+                let index = lines.iter().position(later).unwrap_or(last + 1);
+                lines.insert(index, dependency);
+            }
+            None => {
+                let section = [
+                    "".to_string(),
+                    format!("# Advent of Code {}", new.year),
+                    dependency,
+                ];
+                let earlier = |line: &String| day_name(line).is_some_and(|day| day < name);
+                let after = lines
+                    .iter()
+                    .rposition(earlier)
+                    .or_else(|| table_end(&lines, "[dependencies]"));
+                match after {
+                    Some(after) => {
+                        lines.splice(after + 1..after + 1, section);
+                    }
+                    None => {
+                        lines.extend(["".to_string(), "[dependencies]".to_string()]);
+                        lines.extend(section.into_iter().skip(1));
+                    }
+                }
+            }
+        }
+    }
+    lines.join("\n") + "\n"
+}
+
+/// The last line of a table that isn't blank, the header if it's empty.
+fn table_end(lines: &[String], header: &str) -> Option<usize> {
+    let start = lines.iter().position(|line| line.trim() == header)?;
+    let rest = &lines[start + 1..];
+    let next = rest.iter().position(|line| line.starts_with('['));
+    let table = &rest[..next.unwrap_or(rest.len())];
+    let last = table.iter().rposition(|line| !line.trim().is_empty());
+    Some(start + last.map_or(0, |last| last + 1))
+}
+
+/// The day crate a line lists as a dependency, commented out or not.
+fn day_name(line: &str) -> Option<&str> {
+    let line = line.trim().trim_start_matches("# ");
+    let (name, _) = line.split_once(" = ")?;
+    (name.starts_with("aoc") && name.contains("_day")).then_some(name)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn day(year: usize, day: usize) -> NewDay {
+        NewDay {
+            year,
+            day,
+            a: "i64".into(),
+            b: "String".into(),
+            sample: None,
+        }
+    }
+
+    const MANIFEST: &str = "\
+[workspace]
+members = [
+\t\"utils\",
+\t\"aoc2023/*\",
+]
+
+[dependencies]
+utils = { path = \"utils\" }
+
+# Advent of Code 2023
+aoc2023_day01 = { path = \"aoc2023/day01\" }
+aoc2023_day03 = { path = \"aoc2023/day03\" }
+# aoc2023_day04 = { path = \"aoc2023/day04\" }
+# aoc2023_day07 = { path = \"aoc2023/day07\" }
+";
+
+    #[test]
+    fn already_registered() {
+        assert_eq!(register_dependency(MANIFEST, &day(2023, 1)), MANIFEST);
+        let uncommented = register_dependency(MANIFEST, &day(2023, 4));
+        assert_eq!(
+            uncommented,
+            MANIFEST.replace("# aoc2023_day04", "aoc2023_day04")
+        );
+        assert_eq!(
+            register_dependency(&uncommented, &day(2023, 4)),
+            uncommented
+        );
+    }
+
+    #[test]
+    fn in_order() {
+        let manifest = register_dependency(MANIFEST, &day(2023, 2));
+        let manifest = register_dependency(&manifest, &day(2023, 5));
+        let manifest = register_dependency(&manifest, &day(2023, 9));
+        let days = manifest.lines().filter_map(day_name).collect::<Vec<_>>();
+        let expected = [1, 2, 3, 4, 5, 7, 9].map(|day| format!("aoc2023_day{day:0>2}"));
+        assert_eq!(days, expected);
+    }
+
+    #[test]
+    fn new_year() {
+        let manifest = register_dependency(MANIFEST, &day(2025, 1));
+        let expected = MANIFEST
+            .replace("\t\"aoc2023/*\",\n", "\t\"aoc2023/*\",\n\t\"aoc2025/*\",\n")
+            + "\n# Advent of Code 2025\naoc2025_day01 = { path = \"aoc2025/day01\" }\n";
+        assert_eq!(manifest, expected);
+
+        let manifest = register_dependency(&manifest, &day(2024, 3));
+        let headings = manifest.lines().filter(|line| line.starts_with("# Advent"));
+        let headings = headings.collect::<Vec<_>>();
+        let expected = ["2023", "2024", "2025"].map(|year| format!("# Advent of Code {year}"));
+        assert_eq!(headings, expected);
+    }
+
+    #[test]
+    fn missing_dependencies() {
+        let manifest = "[package]\nname = \"aoc\"\n";
+        let expected = "[package]\nname = \"aoc\"\n\n[dependencies]\n# Advent of Code 2025\n\
+            aoc2025_day01 = { path = \"aoc2025/day01\" }\n";
+        assert_eq!(register_dependency(manifest, &day(2025, 1)), expected);
+
+        let manifest = "[dependencies]\nutils = { path = \"utils\" }\n\n[features]\n";
+        let expected = "[dependencies]\nutils = { path = \"utils\" }\n\n# Advent of Code 2025\n\
+            aoc2025_day01 = { path = \"aoc2025/day01\" }\n\n[features]\n";
+        assert_eq!(register_dependency(manifest, &day(2025, 1)), expected);
+    }
+
+    #[test]
+    fn generated_lib() {
+        let template = include_str!("../template/src/lib.rs");
+        let lib = generate_lib(template, &day(2023, 7)).unwrap();
+        assert!(lib.contains("impl Solution<i64, String> for Day"));
+        assert!(lib.contains("fn part_a(&self) -> Option<i64>"));
+        assert!(lib.contains("fn part_b(&self) -> Option<String>"));
+        assert!(lib.contains("include_str!(\"input_sample.txt\"), 0)]"));
+        assert!(lib.contains("include_str!(\"input_sample.txt\"), Default::default())]"));
+        assert!(lib.contains("test_solution!(aoc2023, day07)"));
+        assert!(!lib.contains("day00"));
+
+        let error = generate_lib("impl Solution<u8, u8> for Day", &day(2023, 7)).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "template no longer contains `<i64, i64>`"
+        );
+    }
+}
@@ -0,0 +1,210 @@
+use std::{collections::BTreeSet, ops::RangeInclusive, str::FromStr};
+
+pub use utils::dynamic::Solve;
+use utils::dynamic::erase;
+
+include!(concat!(env!("OUT_DIR"), "/registry.rs"));
+
+/// The years given to `--year`, either `all` or a list such as `2023,2025`.
+#[derive(Debug, Clone)]
+pub enum Years {
+    All,
+    Only(BTreeSet<usize>),
+}
+
+impl FromStr for Years {
+    type Err = String;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        match text {
+            "all" => Ok(Years::All),
+            _ => parse_list(text).map(Years::Only),
+        }
+    }
+}
+
+/// The days given to `--day`, a list of days and ranges such as `1..=5,9`, each run once in order.
+#[derive(Debug, Clone)]
+pub struct Days(BTreeSet<usize>);
+
+impl FromStr for Days {
+    type Err = String;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        parse_list(text).map(Days)
+    }
+}
+
+/// Parses comma separated numbers and ranges, written `a..=b` or `a..b`, ignoring repeats.
+fn parse_list(text: &str) -> Result<BTreeSet<usize>, String> {
+    let number = |text: &str| {
+        text.trim()
+            .parse::<usize>()
+            .map_err(|err| format!("invalid number `{text}`: {err}"))
+    };
+    let range = |item: &str| -> Result<RangeInclusive<usize>, String> {
+        if let Some((start, end)) = item.split_once("..=") {
+            Ok(number(start)?..=number(end)?)
+        } else if let Some((start, end)) = item.split_once("..") {
+            let end = number(end)?;
+            match end.checked_sub(1) {
+                Some(last) => Ok(number(start)?..=last),
+                None => Err(format!("empty range `{item}`")),
+            }
+        } else {
+            let single = number(item)?;
+            Ok(single..=single)
+        }
+    };
+
+    let mut list = BTreeSet::new();
+    for item in text.split(',') {
+        let range = range(item)?;
+        if range.is_empty() {
+            return Err(format!("empty range `{item}`"));
+        }
+        list.extend(range);
+    }
+    Ok(list)
+}
+
+/// The solutions to run, and the selected days that have none.
+#[derive(Debug, Default)]
+pub struct Selection {
+    pub solutions: Vec<(usize, usize, Solve)>,
+    pub missing: Vec<(usize, usize)>,
+}
+
+/// Resolves the selected years and days into the registered solutions to run.
+///
+/// Without any days, each year runs its latest day, or every day with `all`.
+pub fn resolve(years: &Years, days: Option<&Days>, all: bool) -> Result<Selection, String> {
+    let years = match years {
+        Years::All => known_years(),
+        Years::Only(years) => {
+            let known = known_years();
+            if let Some(year) = years.iter().find(|year| !known.contains(year)) {
+                let known = known.iter().map(usize::to_string).collect::<Vec<_>>();
+                return Err(format!(
+                    "no solutions for {year}, try one of {}",
+                    known.join(", ")
+                ));
+            }
+            years.iter().copied().collect()
+        }
+    };
+
+    let mut selection = Selection::default();
+    for year in years {
+        let registered = registered(year).collect::<Vec<_>>();
+        match days {
+            None if all => selection.solutions.extend(registered),
+            None => selection.solutions.extend(registered.last()),
+            Some(Days(days)) => {
+                for &day in days {
+                    if !(1..=25).contains(&day) {
+                        return Err(format!("there is no day {day}, try 1..=25"));
+                    }
+                    match registered.iter().find(|&&(_, known, _)| known == day) {
+                        Some(&solution) => selection.solutions.push(solution),
+                        None => selection.missing.push((year, day)),
+                    }
+                }
+            }
+        }
+    }
+    Ok(selection)
+}
+
+fn registered(year: usize) -> impl Iterator<Item = (usize, usize, Solve)> {
+    SOLUTIONS
+        .iter()
+        .copied()
+        .filter(move |&(known, _, _)| known == year)
+}
+
+fn known_years() -> Vec<usize> {
+    let mut years = SOLUTIONS
+        .iter()
+        .map(|&(year, _, _)| year)
+        .collect::<Vec<_>>();
+    years.dedup();
+    years
+}
+
+/// The days of a year that have solutions, with consecutive days collapsed into ranges.
+pub fn implemented(year: usize) -> String {
+    let mut ranges: Vec<(usize, usize)> = vec![];
+    for (_, day, _) in registered(year) {
+        match ranges.last_mut() {
+            Some((_, end)) if *end + 1 == day => *end = day,
+            _ => ranges.push((day, day)),
+        }
+    }
+    let ranges = ranges.iter().map(|&(start, end)| match start == end {
+        true => start.to_string(),
+        false => format!("{start}..={end}"),
+    });
+    ranges.collect::<Vec<_>>().join(", ")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn days(text: &str) -> Result<Vec<usize>, String> {
+        text.parse::<Days>()
+            .map(|Days(days)| days.into_iter().collect())
+    }
+
+    fn selected(years: &str, days: &str) -> Selection {
+        let days = days.parse::<Days>().unwrap();
+        resolve(&years.parse().unwrap(), Some(&days), false).unwrap()
+    }
+
+    #[test]
+    fn ranges() {
+        assert_eq!(days("3"), Ok(vec![3]));
+        assert_eq!(days("1..=3, 7"), Ok(vec![1, 2, 3, 7]));
+        assert_eq!(days("1..3"), Ok(vec![1, 2]));
+        assert_eq!(days("5..=5"), Ok(vec![5]));
+    }
+
+    #[test]
+    fn duplicates() {
+        assert_eq!(days("1,1"), Ok(vec![1]));
+        assert_eq!(days("4,1..=3,2"), Ok(vec![1, 2, 3, 4]));
+        let selection = selected("2025", "2,2");
+        assert_eq!(selection.solutions.len(), 1);
+    }
+
+    #[test]
+    fn invalid() {
+        let error = days("1,x").unwrap_err();
+        assert!(error.starts_with("invalid number `x`"), "{error}");
+        assert_eq!(days("3..3"), Err("empty range `3..3`".into()));
+        assert_eq!(days("3..0"), Err("empty range `3..0`".into()));
+        assert_eq!(days("4..=2"), Err("empty range `4..=2`".into()));
+        assert!(days("").is_err());
+        assert!(days("1..=").is_err());
+
+        let years = "2025".parse().unwrap();
+        let out_of_range = "26".parse::<Days>().unwrap();
+        let error = resolve(&years, Some(&out_of_range), false).unwrap_err();
+        assert_eq!(error, "there is no day 26, try 1..=25");
+        let error = resolve(&"1999".parse().unwrap(), None, false).unwrap_err();
+        assert!(error.starts_with("no solutions for 1999"), "{error}");
+    }
+
+    #[test]
+    fn missing() {
+        let selection = selected("2023,2025", "8..=9");
+        let solutions = selection
+            .solutions
+            .iter()
+            .map(|&(year, day, _)| (year, day));
+        let solutions = solutions.collect::<Vec<_>>();
+        assert_eq!(solutions, [(2023, 8), (2025, 8), (2025, 9)]);
+        assert_eq!(selection.missing, [(2023, 9)]);
+    }
+}
@@ -0,0 +1,230 @@
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+    process::Command,
+    thread,
+    time::{Duration, SystemTime},
+};
+
+use serde::Deserialize;
+use utils::input::Input;
+
+const POLL: Duration = Duration::from_millis(300);
+
+/// A part's result, as printed by `--format json`.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+struct Outcome {
+    year: usize,
+    day: usize,
+    part: char,
+    answer: Option<String>,
+    samples: String,
+    verification: String,
+    time_ns: Option<u64>,
+    detail: Option<String>,
+}
+
+type Outcomes = BTreeMap<(usize, usize, char), Outcome>;
+
+/// The files whose changes trigger a rebuild: the days' crates, `utils` and the inputs.
+fn watched(root: &Path, days: &[(usize, usize)], input: &Input) -> Vec<PathBuf> {
+    let mut paths = vec![root.join("utils/src"), root.join("Cargo.toml")];
+    for &(year, day) in days {
+        paths.push(root.join(format!("aoc{year}/day{day:0>2}")));
+        match input {
+            Input::Directory(dir) => paths.push(Input::path_in(dir, year, day)),
+            Input::File(path) => paths.push(path.clone()),
+            Input::Embedded | Input::Text(_) => {}
+        }
+    }
+    paths
+}
+
+/// The modification times of every file under the paths, skipping build output.
+fn snapshot(paths: &[PathBuf]) -> BTreeMap<PathBuf, SystemTime> {
+    let mut times = BTreeMap::new();
+    let mut pending = paths.to_vec();
+    while let Some(path) = pending.pop() {
+        let Ok(metadata) = fs::metadata(&path) else {
+            continue;
+        };
+        if metadata.is_dir() {
+            if path.ends_with("target") {
+                continue;
+            }
+            let entries = fs::read_dir(&path).into_iter().flatten().flatten();
+            pending.extend(entries.map(|entry| entry.path()));
+        } else if let Ok(modified) = metadata.modified() {
+            times.insert(path, modified);
+        }
+    }
+    times
+}
+
+/// The cargo profile that built the binary at `exe`, from its `target/<profile>` directory.
+fn profile(exe: &Path) -> &str {
+    match exe
+        .parent()
+        .and_then(Path::file_name)
+        .and_then(|dir| dir.to_str())
+    {
+        Some("debug") | None => "dev",
+        Some(profile) => profile,
+    }
+}
+
+/// Rebuilds the binary at `exe` with `cargo`, using the profile and features it was built
+/// with, and reruns it with `args`, or explains what went wrong.
+fn rerun(root: &Path, cargo: &str, exe: &Path, args: &[String]) -> Result<Outcomes, String> {
+    let mut build = Command::new(cargo);
+    build.current_dir(root).args([
+        "build",
+        "--quiet",
+        "--bin",
+        "aoc",
+        "--profile",
+        profile(exe),
+    ]);
+    if cfg!(feature = "memory") {
+        build.args(["--features", "memory"]);
+    }
+    let output = build.output().unwrap_or_else(|err| {
+        eprintln!("failed to run {cargo}: {err}");
+        std::process::exit(1);
+    });
+    if !output.status.success() {
+        return Err(String::from_utf8_lossy(&output.stderr).into_owned());
+    }
+
+    let output = Command::new(exe)
+        .args(args)
+        .output()
+        .map_err(|err| format!("failed to run aoc: {err}"))?;
+    eprint!("{}", String::from_utf8_lossy(&output.stderr));
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    stdout
+        .lines()
+        .map(|line| {
+            let outcome = serde_json::from_str::<Outcome>(line)
+                .map_err(|err| format!("unexpected output `{line}`: {err}"))?;
+            Ok(((outcome.year, outcome.day, outcome.part), outcome))
+        })
+        .collect()
+}
+
+fn describe(outcome: &Outcome) -> String {
+    let answer = outcome.answer.as_deref().unwrap_or("-");
+    let time = outcome.time_ns.map_or(String::new(), |ns| {
+        format!("  {:.1?}", Duration::from_nanos(ns))
+    });
+    format!(
+        "{:<9}{answer} [{}]{time}",
+        outcome.samples, outcome.verification
+    )
+}
+
+/// Prints each part, noting how its samples and answer changed since the previous run.
+fn print(outcomes: &Outcomes, previous: &Option<Outcomes>) {
+    for (&(year, day, part), outcome) in outcomes {
+        let change = match previous
+            .as_ref()
+            .map(|previous| previous.get(&(year, day, part)))
+        {
+            None => String::new(),
+            Some(None) => "  (new)".to_string(),
+            Some(Some(old)) if old.samples != outcome.samples || old.answer != outcome.answer => {
+                let answer = old.answer.as_deref().unwrap_or("-");
+                format!("  (was {} {answer})", old.samples)
+            }
+            Some(Some(_)) => "  (unchanged)".to_string(),
+        };
+        println!("{year}/{day:0>2} {part}  {}{change}", describe(outcome));
+        if let Some(detail) = &outcome.detail {
+            println!("           {detail}");
+        }
+    }
+}
+
+/// Rebuilds and reruns the days whenever their sources or inputs change, until interrupted.
+pub fn watch(root: &Path, days: &[(usize, usize)], input: &Input) -> ! {
+    let args = std::env::args()
+        .skip(1)
+        .filter(|arg| arg != "--watch")
+        .chain(["--format".to_string(), "json".to_string()])
+        .collect::<Vec<_>>();
+
+    // The cargo of the toolchain `cargo run` used, which the binary can't know otherwise.
+    let cargo = std::env::var("CARGO").unwrap_or_else(|_| {
+        eprintln!("`--watch` rebuilds with cargo, start it with `cargo run -- --watch`");
+        std::process::exit(1);
+    });
+    // Resolved up front, as rebuilding replaces the running binary.
+    let exe = std::env::current_exe().unwrap_or_else(|err| {
+        eprintln!("failed to find the aoc binary: {err}");
+        std::process::exit(1);
+    });
+    let paths = watched(root, days, input);
+    let mut previous: Option<Outcomes> = None;
+    let mut times = BTreeMap::new();
+    let mut run = 0;
+    loop {
+        let now = loop {
+            let now = snapshot(&paths);
+            if now != times {
+                thread::sleep(POLL);
+                break snapshot(&paths);
+            }
+            thread::sleep(POLL);
+        };
+        times = now;
+        run += 1;
+
+        println!("\n======= run {run} =======");
+        match rerun(root, &cargo, &exe, &args) {
+            Ok(outcomes) => {
+                print(&outcomes, &previous);
+                previous = Some(outcomes);
+            }
+            Err(err) => println!("{}", err.trim_end()),
+        }
+        println!("(watching for changes, ctrl-c to stop)");
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn watched_paths() {
+        let root = Path::new("/aoc");
+        let days = [(2023, 5), (2025, 12)];
+        let inputs = Input::Directory(PathBuf::from("/inputs"));
+        let paths = watched(root, &days, &inputs);
+        let expected = [
+            "/aoc/utils/src",
+            "/aoc/Cargo.toml",
+            "/aoc/aoc2023/day05",
+            "/inputs/2023/day05.txt",
+            "/aoc/aoc2025/day12",
+            "/inputs/2025/day12.txt",
+        ];
+        assert_eq!(paths, expected.map(PathBuf::from));
+
+        let paths = watched(root, &days[..1], &Input::Embedded);
+        assert_eq!(paths.last(), Some(&PathBuf::from("/aoc/aoc2023/day05")));
+        let input = Input::File(PathBuf::from("/tmp/input.txt"));
+        let paths = watched(root, &days[..1], &input);
+        assert_eq!(paths.last(), Some(&PathBuf::from("/tmp/input.txt")));
+    }
+
+    #[test]
+    fn profiles() {
+        assert_eq!(profile(Path::new("/aoc/target/debug/aoc")), "dev");
+        assert_eq!(profile(Path::new("/aoc/target/release/aoc")), "release");
+        assert_eq!(profile(Path::new("/aoc/target/profiling/aoc")), "profiling");
+        assert_eq!(profile(Path::new("aoc")), "dev");
+    }
+}
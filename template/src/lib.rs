@@ -9,15 +9,13 @@ impl Solution<i64, i64> for Day {
     fn meta() -> Meta<i64, i64> {
         Meta::<i64, i64> {
             input: include_str!("input.txt").to_string(),
-            sample_a: include_str!("input_sample.txt").to_string(),
-            sample_b: include_str!("input_sample.txt").to_string(),
-            answer_a: 0,
-            answer_b: 0,
+            samples_a: vec![Sample::new("sample", include_str!("input_sample.txt"), 0)],
+            samples_b: vec![Sample::new("sample", include_str!("input_sample.txt"), 0)],
         }
     }
 
-    fn new(raw: Vec<Rc<str>>) -> Self {
-        Self { raw }
+    fn new(raw: Vec<Rc<str>>) -> ParseResult<Self> {
+        Ok(Self { raw })
     }
 
     fn part_a(&self) -> Option<i64> {
@@ -0,0 +1,86 @@
+//! Lookups, inserts and iteration on a grid the size of a typical puzzle input.
+//!
+//! Run with `cargo bench -p utils`.
+
+use std::{collections::VecDeque, hint::black_box};
+
+use criterion::{Criterion, criterion_group, criterion_main};
+use utils::{
+    direction::DIRS,
+    vector::{Vector, VectorMap, VectorSet},
+};
+
+const SIZE: Vector = Vector::new(141, 141);
+
+fn positions() -> impl Iterator<Item = Vector> {
+    (0..SIZE.y).flat_map(|y| (0..SIZE.x).map(move |x| Vector::new(x, y)))
+}
+
+fn wall(pos: Vector) -> bool {
+    (pos.x * 7 + pos.y * 13) % 5 == 0
+}
+
+fn walls() -> VectorSet {
+    let mut set = VectorSet::new(SIZE);
+    positions().filter(|&pos| wall(pos)).for_each(|pos| {
+        set.insert(pos);
+    });
+    set
+}
+
+fn tiles() -> VectorMap<u8> {
+    let mut map = VectorMap::new(SIZE);
+    positions().for_each(|pos| {
+        map.insert(pos, if wall(pos) { b'#' } else { b'.' });
+    });
+    map
+}
+
+fn set(c: &mut Criterion) {
+    let set = walls();
+    c.bench_function("set contains", |b| {
+        b.iter(|| {
+            positions()
+                .filter(|&pos| set.contains(black_box(pos)))
+                .count()
+        })
+    });
+    c.bench_function("set insert", |b| b.iter(walls));
+    c.bench_function("set iter", |b| b.iter(|| black_box(&set).iter().count()));
+}
+
+fn map(c: &mut Criterion) {
+    let map = tiles();
+    c.bench_function("map get", |b| {
+        b.iter(|| {
+            positions()
+                .filter(|&pos| map.get(black_box(pos)) == Some(&b'#'))
+                .count()
+        })
+    });
+    c.bench_function("map insert", |b| b.iter(tiles));
+    c.bench_function("map iter", |b| b.iter(|| black_box(&map).iter().count()));
+}
+
+/// The hot loop of the grid searches: a breadth first search over the map's open tiles.
+fn search(c: &mut Criterion) {
+    let map = tiles();
+    c.bench_function("bfs", |b| {
+        b.iter(|| {
+            let mut seen = VectorSet::new(SIZE);
+            let mut queue = VecDeque::from([Vector::new(1, 1)]);
+            while let Some(pos) = queue.pop_front() {
+                for dir in DIRS {
+                    let next = pos + dir;
+                    if map.get(next) == Some(&b'.') && seen.insert(next) == Some(true) {
+                        queue.push_back(next);
+                    }
+                }
+            }
+            seen.len()
+        })
+    });
+}
+
+criterion_group!(benches, set, map, search);
+criterion_main!(benches);
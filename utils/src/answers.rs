@@ -0,0 +1,260 @@
+use std::{
+    collections::BTreeMap,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize};
+
+/// The confirmed real-input answers of a year, stored as `answers.toml` with a table per day.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Answers {
+    days: BTreeMap<String, Recorded>,
+}
+
+/// The answers confirmed for one day, along with the input they were confirmed for.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Recorded {
+    /// The [`fingerprint`] of the input, answers for other inputs are unverified.
+    pub input: Option<String>,
+    pub a: Option<String>,
+    pub b: Option<String>,
+}
+
+/// How a real answer compares to the recorded one.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum Verification {
+    Correct,
+    Wrong {
+        expected: String,
+    },
+    #[default]
+    Unverified,
+}
+
+/// Why [`Answers::record`] left a day's answers alone.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Conflict {
+    /// The day's answers were recorded for another input.
+    Input,
+    /// A part already has a different answer.
+    Answer {
+        part: char,
+        recorded: String,
+        answer: String,
+    },
+}
+
+impl Answers {
+    /// The answers file of a year, next to its day crates in `root`.
+    pub fn path_in(root: &Path, year: usize) -> PathBuf {
+        root.join(format!("aoc{year}")).join("answers.toml")
+    }
+
+    /// Reads a year's answers, where a missing file has none recorded.
+    pub fn load(path: &Path) -> io::Result<Self> {
+        match fs::read_to_string(path) {
+            Ok(text) => toml::from_str(&text).map_err(io::Error::other),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(err) => Err(err),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        fs::write(path, toml::to_string(self).map_err(io::Error::other)?)
+    }
+
+    pub fn day(&self, day: usize) -> Recorded {
+        self.days.get(&Self::key(day)).cloned().unwrap_or_default()
+    }
+
+    /// Records a day's answers, keeping any part that has no answer now.
+    ///
+    /// Refuses, changing nothing, if that would replace answers recorded for another input or
+    /// differing ones for this input, unless `force`d.
+    pub fn record(
+        &mut self,
+        day: usize,
+        input: String,
+        a: Option<String>,
+        b: Option<String>,
+        force: bool,
+    ) -> Result<(), Vec<Conflict>> {
+        let recorded = self.days.entry(Self::key(day)).or_default();
+        let mut conflicts = vec![];
+        if recorded.input.as_ref().is_some_and(|known| *known != input)
+            && (recorded.a.is_some() || recorded.b.is_some())
+        {
+            conflicts.push(Conflict::Input);
+        } else {
+            for (part, answer, known) in [('a', &a, &recorded.a), ('b', &b, &recorded.b)] {
+                if let (Some(answer), Some(known)) = (answer, known)
+                    && answer != known
+                {
+                    conflicts.push(Conflict::Answer {
+                        part,
+                        recorded: known.clone(),
+                        answer: answer.clone(),
+                    });
+                }
+            }
+        }
+        if !conflicts.is_empty() && !force {
+            return Err(conflicts);
+        }
+
+        if recorded.input.as_ref() != Some(&input) {
+            *recorded = Recorded::default();
+        }
+        recorded.input = Some(input);
+        recorded.a = a.or(recorded.a.take());
+        recorded.b = b.or(recorded.b.take());
+        Ok(())
+    }
+
+    fn key(day: usize) -> String {
+        format!("day{day:0>2}")
+    }
+}
+
+impl Recorded {
+    /// Checks a part's answer, `'a'` or `'b'`, for the input with the given fingerprint.
+    pub fn verify(&self, input: &str, part: char, answer: Option<&str>) -> Verification {
+        let expected = match part {
+            'a' => &self.a,
+            _ => &self.b,
+        };
+        match (expected, answer) {
+            _ if self.input.as_deref() != Some(input) => Verification::Unverified,
+            (Some(expected), Some(answer)) if expected == answer => Verification::Correct,
+            (Some(expected), _) => Verification::Wrong {
+                expected: expected.clone(),
+            },
+            (None, _) => Verification::Unverified,
+        }
+    }
+}
+
+/// A stable FNV-1a hash of an input, ignoring trailing whitespace.
+pub fn fingerprint(input: &str) -> String {
+    let hash = input
+        .trim_end()
+        .bytes()
+        .fold(0xcbf29ce484222325u64, |hash, byte| {
+            (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+        });
+    format!("{hash:016x}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fingerprint_stable() {
+        assert_eq!(fingerprint(""), "cbf29ce484222325");
+        assert_eq!(fingerprint("a"), "af63dc4c8601ec8c");
+        assert_eq!(fingerprint("a\n"), fingerprint("a"));
+    }
+
+    #[test]
+    fn verify() {
+        let mut answers = Answers::default();
+        answers
+            .record(1, fingerprint("input"), Some("42".to_string()), None, false)
+            .unwrap();
+        let recorded = answers.day(1);
+        let input = fingerprint("input");
+
+        assert_eq!(
+            recorded.verify(&input, 'a', Some("42")),
+            Verification::Correct
+        );
+        assert_eq!(
+            recorded.verify(&input, 'a', Some("41")),
+            Verification::Wrong {
+                expected: "42".to_string()
+            }
+        );
+        assert_eq!(
+            recorded.verify(&input, 'b', Some("1")),
+            Verification::Unverified
+        );
+        assert_eq!(
+            recorded.verify(&fingerprint("other"), 'a', Some("41")),
+            Verification::Unverified
+        );
+        assert_eq!(
+            answers.day(2).verify(&input, 'a', Some("42")),
+            Verification::Unverified
+        );
+    }
+
+    #[test]
+    fn record_keeps_parts() {
+        let mut answers = Answers::default();
+        let x = || "x".to_string();
+        answers
+            .record(1, x(), Some("1".to_string()), None, false)
+            .unwrap();
+        answers
+            .record(1, x(), None, Some("2".to_string()), false)
+            .unwrap();
+        answers
+            .record(1, x(), Some("1".to_string()), None, false)
+            .unwrap();
+        assert_eq!(answers.day(1).a.as_deref(), Some("1"));
+        assert_eq!(answers.day(1).b.as_deref(), Some("2"));
+
+        answers
+            .record(1, "y".to_string(), None, Some("3".to_string()), true)
+            .unwrap();
+        assert_eq!(answers.day(1).a, None);
+    }
+
+    #[test]
+    fn record_refuses_overwrite() {
+        let mut answers = Answers::default();
+        let x = || "x".to_string();
+        answers
+            .record(1, x(), Some("1".to_string()), Some("2".to_string()), false)
+            .unwrap();
+
+        let conflicts = answers.record(1, x(), Some("3".to_string()), None, false);
+        let conflict = Conflict::Answer {
+            part: 'a',
+            recorded: "1".to_string(),
+            answer: "3".to_string(),
+        };
+        assert_eq!(conflicts, Err(vec![conflict]));
+        let conflicts = answers.record(1, "y".to_string(), None, Some("4".to_string()), false);
+        assert_eq!(conflicts, Err(vec![Conflict::Input]));
+        assert_eq!(answers.day(1).a.as_deref(), Some("1"));
+        assert_eq!(answers.day(1).input, Some(x()));
+
+        answers
+            .record(1, x(), Some("3".to_string()), None, true)
+            .unwrap();
+        assert_eq!(answers.day(1).a.as_deref(), Some("3"));
+        assert_eq!(answers.day(1).b.as_deref(), Some("2"));
+    }
+
+    #[test]
+    fn toml_roundtrip() {
+        let mut answers = Answers::default();
+        answers
+            .record(
+                3,
+                "abc".to_string(),
+                Some("1".to_string()),
+                Some("2".to_string()),
+                false,
+            )
+            .unwrap();
+        let text = toml::to_string(&answers).unwrap();
+        assert!(text.starts_with("[day03]\n"), "{text}");
+        let parsed: Answers = toml::from_str(&text).unwrap();
+        assert_eq!(parsed.day(3), answers.day(3));
+    }
+}
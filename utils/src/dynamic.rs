@@ -0,0 +1,542 @@
+use std::{
+    any::type_name,
+    fmt::Display,
+    marker::PhantomData,
+    rc::Rc,
+    sync::mpsc::{self, RecvTimeoutError},
+    thread,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    answers::fingerprint,
+    error::ParseResult,
+    memory::measure,
+    solution::{
+        Check, Failure, Mode, Options, Part, Report, SampleRun, Solution, check, run_samples, split,
+    },
+    trace::{Frames, capture},
+};
+
+/// An object-safe view of a [`Solution`], with answers rendered as strings.
+///
+/// Lets runners and tools drive every day the same way, whatever its answer and parameter types.
+pub trait DynSolution {
+    fn meta(&self) -> DynMeta;
+    /// Parses a real input with the default parameters.
+    fn parse(&self, raw: Vec<Rc<str>>) -> ParseResult<Box<dyn Parsed>>;
+    /// Checks part `'a'` or `'b'` against its samples, each parsed with its own parameters.
+    fn check(&self, part: char) -> Check;
+    /// Solves every sample of part `'a'` or `'b'`, like [`check`](DynSolution::check).
+    fn samples(&self, part: char) -> Vec<SampleRun>;
+}
+
+/// A parsed input, ready to solve either part.
+pub trait Parsed {
+    fn part_a(&self) -> Option<String>;
+    fn part_b(&self) -> Option<String>;
+}
+
+/// The [`Meta`](crate::solution::Meta) of a solution, with sample answers rendered as strings.
+#[derive(Debug, Clone)]
+pub struct DynMeta {
+    /// The solution's type, e.g. `aoc2025_day01::Day`.
+    pub name: &'static str,
+    pub input: String,
+    pub samples_a: Vec<DynSample>,
+    pub samples_b: Vec<DynSample>,
+}
+
+#[derive(Debug, Clone)]
+pub struct DynSample {
+    pub name: &'static str,
+    pub input: String,
+    pub answer: String,
+}
+
+/// Wraps the solution `S` to implement [`DynSolution`], see [`erase`].
+pub struct Erased<S, T, U, P>(PhantomData<(S, T, U, P)>);
+
+impl<S, T, U, P> Default for Erased<S, T, U, P> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+/// Erases the types of a solution, e.g. `erase::<Day, _, _, _>()`.
+pub fn erase<S, T, U, P>() -> Box<dyn DynSolution>
+where
+    S: Solution<T, U, P> + 'static,
+    T: Display + Eq + 'static,
+    U: Display + Eq + 'static,
+    P: Clone + Default + 'static,
+{
+    Box::new(Erased::<S, T, U, P>::default())
+}
+
+struct Solved<S, T, U, P>(S, PhantomData<(T, U, P)>);
+
+impl<S, T, U, P> Parsed for Solved<S, T, U, P>
+where
+    S: Solution<T, U, P>,
+    T: Display + Eq,
+    U: Display + Eq,
+    P: Clone + Default,
+{
+    fn part_a(&self) -> Option<String> {
+        self.0.part_a().map(|answer| answer.to_string())
+    }
+
+    fn part_b(&self) -> Option<String> {
+        self.0.part_b().map(|answer| answer.to_string())
+    }
+}
+
+impl<S, T, U, P> DynSolution for Erased<S, T, U, P>
+where
+    S: Solution<T, U, P> + 'static,
+    T: Display + Eq + 'static,
+    U: Display + Eq + 'static,
+    P: Clone + Default + 'static,
+{
+    fn meta(&self) -> DynMeta {
+        let meta = S::meta();
+        let samples = |name, input, answer: &dyn Display| DynSample {
+            name,
+            input,
+            answer: answer.to_string(),
+        };
+        DynMeta {
+            name: type_name::<S>(),
+            input: meta.input,
+            samples_a: (meta.samples_a.into_iter())
+                .map(|sample| samples(sample.name, sample.input, &sample.answer))
+                .collect(),
+            samples_b: (meta.samples_b.into_iter())
+                .map(|sample| samples(sample.name, sample.input, &sample.answer))
+                .collect(),
+        }
+    }
+
+    fn parse(&self, raw: Vec<Rc<str>>) -> ParseResult<Box<dyn Parsed>> {
+        let solution = S::new_with(raw, &P::default())?;
+        Ok(Box::new(Solved::<S, T, U, P>(solution, PhantomData)))
+    }
+
+    fn check(&self, part: char) -> Check {
+        let meta = S::meta();
+        match part {
+            'a' => check(&meta.samples_a, |raw, params| {
+                S::new_with(raw, params).map(|sample| sample.part_a())
+            }),
+            _ => check(&meta.samples_b, |raw, params| {
+                S::new_with(raw, params).map(|sample| sample.part_b())
+            }),
+        }
+    }
+
+    fn samples(&self, part: char) -> Vec<SampleRun> {
+        let meta = S::meta();
+        match part {
+            'a' => run_samples(&meta.samples_a, |raw, params| {
+                S::new_with(raw, params).map(|sample| sample.part_a())
+            }),
+            _ => run_samples(&meta.samples_b, |raw, params| {
+                S::new_with(raw, params).map(|sample| sample.part_b())
+            }),
+        }
+    }
+}
+
+/// Creates a day's solution, as registered with the runner.
+pub type Solve = fn() -> Box<dyn DynSolution>;
+
+/// A phase of running a day, reported to the runner as it completes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Stage {
+    Parse,
+    Samples(char),
+    Solve(char),
+    Done,
+}
+
+/// Stack size of the threads running days under a time limit, enough for deep recursion.
+const STACK: usize = 8 << 20;
+
+/// Runs a day: checks its samples, then times parsing and solving the real input.
+///
+/// With a [`timeout`](Options::timeout), each phase runs on a worker thread and one that
+/// doesn't finish in time is reported as timed out. The worker can't be stopped, so it is left
+/// running while the other part is solved on a new worker.
+pub fn run(solve: Solve, options: &Options) -> Report {
+    match options.timeout {
+        Some(limit) => run_limited(solve, options, limit),
+        None => run_parts(&*solve(), options, &options.parts(), &mut |_, _| {}),
+    }
+}
+
+fn run_limited(solve: Solve, options: &Options, limit: Duration) -> Report {
+    let mut done: Option<Report> = None;
+    let mut pending = options.parts();
+    while !pending.is_empty() {
+        let parts = std::mem::take(&mut pending);
+        let (sender, receiver) = mpsc::channel();
+        let (worker_options, worker_parts) = (options.clone(), parts.clone());
+        let worker = thread::Builder::new().stack_size(STACK).spawn(move || {
+            let mut progress = |stage, report: &Report| {
+                let _ = sender.send((stage, report.clone()));
+            };
+            let report = run_parts(&*solve(), &worker_options, &worker_parts, &mut progress);
+            progress(Stage::Done, &report);
+        });
+        if worker.is_err() {
+            return Report::failed(options.year, options.day, Failure::Panicked);
+        }
+
+        let mut last = None;
+        let (mut report, stuck) = loop {
+            match receiver.recv_timeout(limit) {
+                Ok((Stage::Done, report)) => break (report, None),
+                Ok(step) => last = Some(step),
+                Err(RecvTimeoutError::Timeout) => match last {
+                    Some((stage, report)) => break (report, Some(stage)),
+                    // Only the samples run, so it's stuck on the first part's, right after
+                    // where parsing would be.
+                    None if options.mode == Mode::Samples => {
+                        break (Report::new(options.year, options.day), Some(Stage::Parse));
+                    }
+                    // Stuck parsing, which a later worker only is when slowed down.
+                    None => match done {
+                        Some(mut previous) => {
+                            for &part in &parts {
+                                previous.part_mut(part).samples = Check::TimedOut;
+                            }
+                            return previous.verified(&options.answers);
+                        }
+                        None => {
+                            let failure = Failure::TimedOut(limit);
+                            return Report::failed(options.year, options.day, failure);
+                        }
+                    },
+                },
+                Err(RecvTimeoutError::Disconnected) => {
+                    return Report::failed(options.year, options.day, Failure::Panicked);
+                }
+            }
+        };
+        if report.failure.is_some() {
+            return report;
+        }
+
+        // Parts run in order, so the stuck phase is the one after the last that completed.
+        let index = |part| parts.iter().position(|&p| p == part).unwrap();
+        let stuck = match stuck {
+            Some(Stage::Parse) => Some((0, true)),
+            Some(Stage::Samples(part)) => Some((index(part), false)),
+            Some(Stage::Solve(part)) => Some((index(part) + 1, true)),
+            Some(Stage::Done) | None => None,
+        };
+        if let Some((stuck, in_samples)) = stuck
+            && let Some(&part) = parts.get(stuck)
+        {
+            match in_samples {
+                true => report.part_mut(part).samples = Check::TimedOut,
+                false => report.part_mut(part).timed_out = true,
+            }
+            pending = parts[stuck + 1..].to_vec();
+        }
+
+        if let Some(previous) = done {
+            for part in ['a', 'b'].into_iter().filter(|part| !parts.contains(part)) {
+                *report.part_mut(part) = previous.part(part).clone();
+            }
+            report.trace.splice(0..0, previous.trace);
+        }
+        done = Some(report);
+    }
+    done.unwrap().verified(&options.answers)
+}
+
+/// Runs the day's `parts` in order, reporting each stage to `progress`.
+fn run_parts(
+    solution: &dyn DynSolution,
+    options: &Options,
+    parts: &[char],
+    progress: &mut dyn FnMut(Stage, &Report),
+) -> Report {
+    let mut report = Report::new(options.year, options.day);
+    let keep = |report: &mut Report, phase, frames: Frames| {
+        if !frames.is_empty() {
+            report.trace.push((phase, frames));
+        }
+    };
+
+    if options.mode == Mode::Samples {
+        for &part in parts {
+            let (runs, frames) = capture(options.trace, || solution.samples(part));
+            keep(&mut report, phase_names(part).0, frames);
+            let result = report.part_mut(part);
+            result.samples = Check::of(&runs);
+            result.sample_runs = runs;
+            progress(Stage::Samples(part), &report);
+            progress(Stage::Solve(part), &report);
+        }
+        return report;
+    }
+
+    let meta = solution.meta();
+    let input = match options.input.load(options.year, options.day, &meta.input) {
+        Ok(input) => input,
+        Err(err) => {
+            let failure = Failure::Input(err.to_string());
+            return Report::failed(options.year, options.day, failure);
+        }
+    };
+
+    report.input = Some(fingerprint(&input));
+    let (((real, duration), memory), frames) = capture(options.trace, || {
+        measure(|| {
+            let start = Instant::now();
+            (solution.parse(split(input)), start.elapsed())
+        })
+    });
+    let real = match real {
+        Ok(real) => real,
+        Err(err) => return Report::failed(options.year, options.day, Failure::Parse(err)),
+    };
+    keep(&mut report, "parse", frames);
+    report.parse = Some(duration);
+    report.parse_memory = memory;
+    progress(Stage::Parse, &report);
+
+    for &part in parts {
+        let (samples_phase, phase) = phase_names(part);
+        let samples = match options.mode {
+            Mode::Real => Check::Skipped,
+            _ => {
+                let (samples, frames) = capture(options.trace, || solution.check(part));
+                keep(&mut report, samples_phase, frames);
+                samples
+            }
+        };
+        report.part_mut(part).samples = samples.clone();
+        progress(Stage::Samples(part), &report);
+
+        if let Check::Passed | Check::Skipped = samples {
+            let timed = || match part {
+                'a' => Part::timed(samples, || real.part_a()),
+                _ => Part::timed(samples, || real.part_b()),
+            };
+            let (result, frames) = capture(options.trace, timed);
+            keep(&mut report, phase, frames);
+            *report.part_mut(part) = result;
+        }
+        progress(Stage::Solve(part), &report);
+    }
+    report.verified(&options.answers)
+}
+
+/// The trace phases of a part's samples and of solving it.
+fn phase_names(part: char) -> (&'static str, &'static str) {
+    match part {
+        'a' => ("samples a", "a"),
+        _ => ("samples b", "b"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{error::Context, solution::Meta, solution::Sample};
+
+    /// Sums the input, or counts its lines when the `count` parameter is set.
+    struct Sum(Vec<i64>, bool);
+
+    impl Solution<i64, usize, bool> for Sum {
+        fn meta() -> Meta<i64, usize, bool> {
+            Meta {
+                input: "4\n5".to_string(),
+                samples_a: vec![Sample::new("sum", "1\n2", 3)],
+                samples_b: vec![
+                    Sample::new("lines", "1\n2\n3", 3).with_params(true),
+                    Sample::new("wrong", "1", 2),
+                ],
+            }
+        }
+
+        fn new(raw: Vec<Rc<str>>) -> ParseResult<Self> {
+            Self::new_with(raw, &false)
+        }
+
+        fn new_with(raw: Vec<Rc<str>>, count: &bool) -> ParseResult<Self> {
+            let numbers = raw
+                .iter()
+                .enumerate()
+                .map(|(index, line)| line.parse().context(index, "number"));
+            Ok(Self(numbers.collect::<ParseResult<_>>()?, *count))
+        }
+
+        fn part_a(&self) -> Option<i64> {
+            Some(self.0.iter().sum())
+        }
+
+        fn part_b(&self) -> Option<usize> {
+            Some(if self.1 { self.0.len() } else { 0 })
+        }
+    }
+
+    /// Takes forever on the real input's part a, but not on the samples.
+    struct Stuck(bool);
+
+    impl Solution<i64, i64, bool> for Stuck {
+        fn meta() -> Meta<i64, i64, bool> {
+            Meta {
+                input: "real".to_string(),
+                samples_a: vec![Sample::new("quick", "sample", 1).with_params(true)],
+                samples_b: vec![Sample::new("quick", "sample", 2).with_params(true)],
+            }
+        }
+
+        fn new(raw: Vec<Rc<str>>) -> ParseResult<Self> {
+            Self::new_with(raw, &false)
+        }
+
+        fn new_with(_raw: Vec<Rc<str>>, quick: &bool) -> ParseResult<Self> {
+            Ok(Self(*quick))
+        }
+
+        fn part_a(&self) -> Option<i64> {
+            if !self.0 {
+                std::thread::sleep(Duration::from_secs(30));
+            }
+            Some(1)
+        }
+
+        fn part_b(&self) -> Option<i64> {
+            Some(2)
+        }
+    }
+
+    /// Takes forever on part a's sample.
+    struct StuckSample;
+
+    impl Solution<i64, i64> for StuckSample {
+        fn meta() -> Meta<i64, i64> {
+            Meta {
+                input: "real".to_string(),
+                samples_a: vec![Sample::new("slow", "sample", 1)],
+                samples_b: vec![Sample::new("quick", "sample", 2)],
+            }
+        }
+
+        fn new(_raw: Vec<Rc<str>>) -> ParseResult<Self> {
+            Ok(Self)
+        }
+
+        fn part_a(&self) -> Option<i64> {
+            std::thread::sleep(Duration::from_secs(30));
+            Some(1)
+        }
+
+        fn part_b(&self) -> Option<i64> {
+            Some(2)
+        }
+    }
+
+    #[test]
+    fn meta() {
+        let meta = erase::<Sum, _, _, _>().meta();
+        assert!(meta.name.ends_with("Sum"));
+        assert_eq!(meta.samples_b[0].answer, "3");
+    }
+
+    #[test]
+    fn parse() {
+        let solution = erase::<Sum, _, _, _>();
+        let parsed = solution.parse(split("4\n5".into())).unwrap();
+        assert_eq!(parsed.part_a().as_deref(), Some("9"));
+        assert_eq!(parsed.part_b().as_deref(), Some("0"));
+        assert!(solution.parse(split("x".into())).is_err());
+    }
+
+    #[test]
+    fn check() {
+        let solution = erase::<Sum, _, _, _>();
+        assert!(matches!(solution.check('a'), Check::Passed));
+        assert!(matches!(
+            solution.check('b'),
+            Check::Failed { name: "wrong", .. }
+        ));
+    }
+
+    #[test]
+    fn runs() {
+        let report = run(erase::<Sum, _, _, _>, &Options::default());
+        assert!(report.failure.is_none());
+        assert_eq!(report.part_a.answer.as_deref(), Some("9"));
+        assert!(matches!(report.part_b.samples, Check::Failed { .. }));
+        assert_eq!(report.part_b.answer, None);
+    }
+
+    #[test]
+    fn times_out() {
+        let options = Options {
+            timeout: Some(Duration::from_millis(200)),
+            ..Default::default()
+        };
+        let report = run(erase::<Stuck, _, _, _>, &options);
+        assert!(report.failure.is_none());
+        assert!(matches!(report.part_a.samples, Check::Passed));
+        assert!(report.part_a.timed_out);
+        assert_eq!(report.part_a.answer, None);
+        assert_eq!(report.part_b.answer.as_deref(), Some("2"));
+        assert!(!report.part_b.timed_out);
+    }
+
+    #[test]
+    fn samples_only() {
+        let options = Options {
+            mode: Mode::Samples,
+            ..Default::default()
+        };
+        let report = run(erase::<Sum, _, _, _>, &options);
+        assert_eq!(report.parse, None);
+        assert!(matches!(report.part_a.samples, Check::Passed));
+        let runs = &report.part_b.sample_runs;
+        assert_eq!(runs.len(), 2);
+        assert!(runs[0].passed());
+        assert!(!runs[1].passed());
+        assert!(matches!(
+            report.part_b.samples,
+            Check::Failed { name: "wrong", .. }
+        ));
+    }
+
+    #[test]
+    fn samples_time_out() {
+        let options = Options {
+            mode: Mode::Samples,
+            timeout: Some(Duration::from_millis(200)),
+            ..Default::default()
+        };
+        let report = run(erase::<StuckSample, _, _, _>, &options);
+        assert!(report.failure.is_none());
+        assert!(matches!(report.part_a.samples, Check::TimedOut));
+        assert!(!report.part_a.timed_out);
+        assert!(matches!(report.part_b.samples, Check::Passed));
+        assert_eq!(report.part_b.sample_runs.len(), 1);
+    }
+
+    #[test]
+    fn single_real_part() {
+        let options = Options {
+            mode: Mode::Real,
+            part: Some('b'),
+            ..Default::default()
+        };
+        let report = run(erase::<Sum, _, _, _>, &options);
+        assert!(report.part_a.skipped());
+        assert!(matches!(report.part_b.samples, Check::Skipped));
+        assert_eq!(report.part_b.answer.as_deref(), Some("0"));
+    }
+}
@@ -0,0 +1,139 @@
+use std::{fmt::Display, str::FromStr};
+
+/// An error in a puzzle input, pointing at where it happened when known.
+///
+/// Lines and columns are stored zero-based, as produced by `enumerate`, and displayed one-based.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: Option<usize>,
+    pub column: Option<usize>,
+    pub message: String,
+}
+
+pub type ParseResult<T> = Result<T, ParseError>;
+
+impl ParseError {
+    /// An error about the input as a whole.
+    pub fn new(message: impl Into<String>) -> Self {
+        Self {
+            line: None,
+            column: None,
+            message: message.into(),
+        }
+    }
+
+    pub fn on_line(line: usize, message: impl Into<String>) -> Self {
+        Self {
+            line: Some(line),
+            column: None,
+            message: message.into(),
+        }
+    }
+
+    pub fn at(line: usize, column: usize, message: impl Into<String>) -> Self {
+        Self {
+            line: Some(line),
+            column: Some(column),
+            message: message.into(),
+        }
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match (self.line, self.column) {
+            (Some(line), Some(column)) => write!(f, "line {}, column {}: ", line + 1, column + 1)?,
+            (Some(line), None) => write!(f, "line {}: ", line + 1)?,
+            _ => {}
+        }
+        write!(f, "{}", self.message)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Converts a failed lookup or conversion into a [`ParseError`] on a given line.
+pub trait Context<T> {
+    fn context(self, line: usize, what: &str) -> ParseResult<T>;
+}
+
+impl<T> Context<T> for Option<T> {
+    fn context(self, line: usize, what: &str) -> ParseResult<T> {
+        self.ok_or_else(|| ParseError::on_line(line, format!("missing {what}")))
+    }
+}
+
+impl<T, E: Display> Context<T> for Result<T, E> {
+    fn context(self, line: usize, what: &str) -> ParseResult<T> {
+        self.map_err(|err| ParseError::on_line(line, format!("invalid {what}: {err}")))
+    }
+}
+
+/// The column at which `part`, a slice of `text`, starts.
+pub fn column(text: &str, part: &str) -> usize {
+    (part.as_ptr() as usize)
+        .saturating_sub(text.as_ptr() as usize)
+        .min(text.len())
+}
+
+/// Parses `field`, a slice of the line `text`, pointing at it on failure.
+pub fn parse_field<F>(line: usize, text: &str, field: &str) -> ParseResult<F>
+where
+    F: FromStr,
+    F::Err: Display,
+{
+    field.parse().map_err(|err| {
+        ParseError::at(
+            line,
+            column(text, field),
+            format!("invalid `{field}`: {err}"),
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display() {
+        assert_eq!(ParseError::new("empty").to_string(), "empty");
+        assert_eq!(ParseError::on_line(0, "bad").to_string(), "line 1: bad");
+        assert_eq!(
+            ParseError::at(2, 4, "bad").to_string(),
+            "line 3, column 5: bad"
+        );
+    }
+
+    #[test]
+    fn context_option() {
+        let error = None::<i64>.context(3, "a `,`").unwrap_err();
+        assert_eq!(error, ParseError::on_line(3, "missing a `,`"));
+    }
+
+    #[test]
+    fn context_result() {
+        let error = "x".parse::<i64>().context(1, "number").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 2: invalid number: invalid digit found in string"
+        );
+    }
+
+    #[test]
+    fn column_of_slice() {
+        let text = "12,ab";
+        let (_, right) = text.split_once(',').unwrap();
+        assert_eq!(column(text, right), 3);
+    }
+
+    #[test]
+    fn parse_field_column() {
+        let text = "12,ab";
+        let (left, right) = text.split_once(',').unwrap();
+        assert_eq!(parse_field::<i64>(0, text, left), Ok(12));
+        let error = parse_field::<i64>(0, text, right).unwrap_err();
+        assert_eq!((error.line, error.column), (Some(0), Some(3)));
+        assert_eq!(error.message, "invalid `ab`: invalid digit found in string");
+    }
+}
@@ -22,27 +22,6 @@ impl GaussJordan {
         self.matrix[row][col] = value;
     }
 
-    pub fn pretty_print(&self) {
-        for row in &self.matrix {
-            println!("{:?}", row);
-        }
-
-        for row in &self.matrix {
-            for (col, v) in row.iter().enumerate() {
-                if col == self.matrix[0].len() - 1 {
-                    println!("{}", v);
-                    continue;
-                }
-                let c = match v {
-                    0.0 => ' ',
-                    1.0 => 'x',
-                    _ => '?',
-                };
-                print!("{c} ");
-            }
-        }
-    }
-
     fn find_pivot(&self, d: usize) -> Option<usize> {
         (d..self.matrix.len()).find(|&r| self.matrix[r][d] != 0.0)
     }
@@ -87,6 +66,32 @@ impl GaussJordan {
     }
 }
 
+/// The matrix's rows, then its pattern of `x` for ones and `?` for other non-zero coefficients
+/// followed by the augmented column.
+impl std::fmt::Display for GaussJordan {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for row in &self.matrix {
+            writeln!(f, "{row:?}")?;
+        }
+
+        for row in &self.matrix {
+            let Some((last, coefficients)) = row.split_last() else {
+                continue;
+            };
+            for v in coefficients {
+                let c = match v {
+                    0.0 => ' ',
+                    1.0 => 'x',
+                    _ => '?',
+                };
+                write!(f, "{c} ")?;
+            }
+            writeln!(f, "{last}")?;
+        }
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -128,4 +133,11 @@ mod tests {
             ]
         )
     }
+
+    #[test]
+    fn display() {
+        let gauss = GaussJordan::from_matrix(&[vec![1.0, 2.0, 10.0], vec![0.0, 1.0, 8.0]]);
+        let expected = "[1.0, 2.0, 10.0]\n[0.0, 1.0, 8.0]\nx ? 10\n  x 8\n";
+        assert_eq!(gauss.to_string(), expected);
+    }
 }
@@ -0,0 +1,75 @@
+use std::{
+    fs,
+    io::{self, ErrorKind},
+    path::{Path, PathBuf},
+};
+
+/// Where a day's puzzle input is read from at runtime.
+#[derive(Debug, Clone, Default)]
+pub enum Input {
+    /// The `input.txt` embedded into the day's crate at compile time.
+    #[default]
+    Embedded,
+    /// An inputs directory laid out as `{dir}/{year}/day{day}.txt`.
+    /// Falls back to the embedded input when the day has no file there.
+    Directory(PathBuf),
+    /// A specific input file.
+    File(PathBuf),
+    /// Input text that has already been read, e.g. from stdin.
+    Text(String),
+}
+
+impl Input {
+    /// The path of a day's input within an inputs directory.
+    pub fn path_in(dir: &Path, year: usize, day: usize) -> PathBuf {
+        dir.join(year.to_string()).join(format!("day{day:0>2}.txt"))
+    }
+
+    /// Resolves the input for a given day, using `embedded` when nothing else is available.
+    pub fn load(&self, year: usize, day: usize, embedded: &str) -> io::Result<String> {
+        match self {
+            Self::Embedded => Ok(embedded.to_string()),
+            Self::Directory(dir) => match fs::read_to_string(Self::path_in(dir, year, day)) {
+                Err(err) if err.kind() == ErrorKind::NotFound => Ok(embedded.to_string()),
+                result => result,
+            },
+            Self::File(path) => fs::read_to_string(path),
+            Self::Text(text) => Ok(text.clone()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn path_in() {
+        let path = Input::path_in(Path::new("inputs"), 2025, 3);
+        assert_eq!(path, Path::new("inputs/2025/day03.txt"));
+    }
+
+    #[test]
+    fn embedded() {
+        let input = Input::Embedded.load(2025, 1, "embedded").unwrap();
+        assert_eq!(input, "embedded");
+    }
+
+    #[test]
+    fn directory_fallback() {
+        let input = Input::Directory(PathBuf::from("/nonexistent"));
+        assert_eq!(input.load(2025, 1, "embedded").unwrap(), "embedded");
+    }
+
+    #[test]
+    fn missing_file() {
+        let input = Input::File(PathBuf::from("/nonexistent/input.txt"));
+        assert!(input.load(2025, 1, "embedded").is_err());
+    }
+
+    #[test]
+    fn text() {
+        let input = Input::Text("text".to_string());
+        assert_eq!(input.load(2025, 1, "embedded").unwrap(), "text");
+    }
+}
@@ -1,10 +1,18 @@
+pub mod answers;
 pub mod countable;
 pub mod direction;
 pub mod disjointset;
+pub mod dynamic;
+pub mod error;
 pub mod gaussjordan;
 pub mod graph;
+pub mod input;
+pub mod memory;
+pub mod parse;
 pub mod prelude;
 pub mod ranges;
+pub mod search;
 pub mod solution;
+pub mod trace;
 pub mod vector;
 pub mod vector3;
@@ -0,0 +1,182 @@
+//! Heap usage measured by the counting allocator [`Counting`], when a binary installs it.
+//!
+//! Usage is counted per thread, so days run on separate threads don't skew each other.
+
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    cell::Cell,
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+/// The peak heap usage and number of allocations of some work, see [`measure`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Memory {
+    /// The most bytes allocated at once, beyond those allocated before the work started.
+    pub peak: usize,
+    pub allocations: usize,
+}
+
+impl Memory {
+    /// The usage of running `self` and then `other`.
+    pub fn then(self, other: Memory) -> Memory {
+        Memory {
+            peak: self.peak.max(other.peak),
+            allocations: self.allocations + other.allocations,
+        }
+    }
+}
+
+/// A global allocator that counts each thread's allocations, for [`measure`]:
+///
+/// ```ignore
+/// #[global_allocator]
+/// static ALLOCATOR: utils::memory::Counting = utils::memory::Counting;
+/// ```
+pub struct Counting;
+
+static INSTALLED: AtomicBool = AtomicBool::new(false);
+
+#[derive(Clone, Copy)]
+struct Usage {
+    current: isize,
+    peak: isize,
+    allocations: usize,
+}
+
+thread_local! {
+    static USAGE: Cell<Usage> = const {
+        Cell::new(Usage {
+            current: 0,
+            peak: 0,
+            allocations: 0,
+        })
+    };
+}
+
+fn count(change: isize, allocations: usize) {
+    // Fails while the thread is being torn down, where nothing is measured anyway.
+    let _ = USAGE.try_with(|usage| {
+        let mut next = usage.get();
+        next.current += change;
+        next.peak = next.peak.max(next.current);
+        next.allocations += allocations;
+        usage.set(next);
+    });
+}
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        INSTALLED.store(true, Ordering::Relaxed);
+        count(layout.size() as isize, 1);
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        INSTALLED.store(true, Ordering::Relaxed);
+        count(layout.size() as isize, 1);
+        unsafe { System.alloc_zeroed(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        count(-(layout.size() as isize), 0);
+        unsafe { System.dealloc(ptr, layout) }
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        count(new_size as isize - layout.size() as isize, 1);
+        unsafe { System.realloc(ptr, layout, new_size) }
+    }
+}
+
+/// Whether [`Counting`] is the global allocator, once anything was allocated.
+pub fn installed() -> bool {
+    INSTALLED.load(Ordering::Relaxed)
+}
+
+/// Runs `f`, measuring its heap usage on this thread if [`Counting`] is installed.
+pub fn measure<R>(f: impl FnOnce() -> R) -> (R, Option<Memory>) {
+    if !installed() {
+        return (f(), None);
+    }
+
+    let before = USAGE.get();
+    USAGE.set(Usage {
+        peak: before.current,
+        ..before
+    });
+    let result = f();
+    let after = USAGE.get();
+    // Keeps the outer peak for measurements around this one.
+    USAGE.set(Usage {
+        peak: after.peak.max(before.peak),
+        ..after
+    });
+
+    let memory = Memory {
+        peak: (after.peak - before.current).max(0) as usize,
+        allocations: after.allocations - before.allocations,
+    };
+    (result, Some(memory))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[global_allocator]
+    static ALLOCATOR: Counting = Counting;
+
+    #[test]
+    fn measures() {
+        let (sum, memory) = measure(|| {
+            let big = std::hint::black_box(vec![1u8; 4096]);
+            let small = std::hint::black_box(vec![1u8; 16]);
+            big.len() + small.len()
+        });
+        let memory = memory.unwrap();
+        assert_eq!(sum, 4112);
+        assert_eq!(memory.allocations, 2);
+        assert_eq!(memory.peak, 4112);
+    }
+
+    #[test]
+    fn peak_after_freeing() {
+        let (_, memory) = measure(|| {
+            drop(vec![1u8; 4096]);
+            vec![1u8; 16]
+        });
+        assert_eq!(memory.unwrap().peak, 4096);
+    }
+
+    #[test]
+    fn nested() {
+        let ((_, inner), outer) = measure(|| {
+            let big = vec![1u8; 4096];
+            let inner = measure(|| vec![1u8; 16]);
+            drop(big);
+            inner
+        });
+        assert_eq!(inner.unwrap().peak, 16);
+        assert_eq!(outer.unwrap().peak, 4112);
+        assert_eq!(outer.unwrap().allocations, 2);
+    }
+
+    #[test]
+    fn then() {
+        let parse = Memory {
+            peak: 10,
+            allocations: 2,
+        };
+        let part = Memory {
+            peak: 5,
+            allocations: 3,
+        };
+        assert_eq!(
+            parse.then(part),
+            Memory {
+                peak: 10,
+                allocations: 5
+            }
+        );
+    }
+}
@@ -0,0 +1,295 @@
+//! Combinators for the shapes puzzle inputs come in, with errors pointing at the offending text.
+//!
+//! Each line is wrapped in a [`Field`], which is split into smaller fields and finally parsed:
+//!
+//! ```ignore
+//! let (key, values) = field.key_values::<i64>()?; // `seeds: 79 14 55 13`
+//! let ranges = field.list::<RangeInclusive<i64>>(",")?; // `11-22,95-115`
+//! let [x, y, z] = field.array::<i64, 3>(",")?; // `162,817,812`
+//! ```
+
+use std::{fmt::Display, ops::RangeInclusive, rc::Rc, str::FromStr};
+
+use crate::{
+    error::{ParseError, ParseResult, column},
+    vector::Vector,
+    vector3::Vector3,
+};
+
+/// A slice of an input line, remembering where it is for error messages.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Field<'a> {
+    line: usize,
+    text: &'a str,
+    field: &'a str,
+}
+
+impl<'a> Field<'a> {
+    /// The whole of line `line`, zero-based, with the given text.
+    pub fn new(line: usize, text: &'a str) -> Self {
+        Self {
+            line,
+            text,
+            field: text,
+        }
+    }
+
+    pub fn as_str(&self) -> &'a str {
+        self.field
+    }
+
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    /// An error pointing at the start of this field.
+    pub fn error(&self, message: impl Into<String>) -> ParseError {
+        ParseError::at(self.line, column(self.text, self.field), message)
+    }
+
+    fn slice(&self, field: &'a str) -> Self {
+        Self { field, ..*self }
+    }
+
+    /// Parses the field with [`FromStr`].
+    pub fn parse<F>(&self) -> ParseResult<F>
+    where
+        F: FromStr,
+        F::Err: Display,
+    {
+        self.field
+            .parse()
+            .map_err(|err| self.error(format!("invalid `{}`: {err}", self.field)))
+    }
+
+    /// Parses the field as one of the shapes of [`FromField`].
+    pub fn get<T: FromField>(&self) -> ParseResult<T> {
+        T::from_field(*self)
+    }
+
+    pub fn trim(&self) -> Self {
+        self.slice(self.field.trim())
+    }
+
+    /// The field after `prefix`, which it must start with.
+    pub fn strip_prefix(&self, prefix: &str) -> ParseResult<Self> {
+        match self.field.strip_prefix(prefix) {
+            Some(rest) => Ok(self.slice(rest)),
+            None => Err(self.error(format!("expected `{prefix}` at `{}`", self.field))),
+        }
+    }
+
+    /// The fields on either side of the first `separator`, which must be present.
+    pub fn split_once(&self, separator: &str) -> ParseResult<(Self, Self)> {
+        match self.field.split_once(separator) {
+            Some((left, right)) => Ok((self.slice(left), self.slice(right))),
+            None => Err(self.error(format!("missing `{separator}` in `{}`", self.field))),
+        }
+    }
+
+    /// The fields between each `separator`, where `" "` splits on any run of whitespace.
+    pub fn split(&self, separator: &'a str) -> impl Iterator<Item = Self> + use<'a> {
+        let this = *self;
+        let fields: Box<dyn Iterator<Item = &'a str>> = match separator {
+            " " => Box::new(self.field.split_whitespace()),
+            _ => Box::new(self.field.split(separator)),
+        };
+        fields.map(move |field| this.slice(field))
+    }
+
+    /// Every value between each `separator`, see [`split`](Field::split).
+    pub fn list<T: FromField>(&self, separator: &'a str) -> ParseResult<Vec<T>> {
+        self.split(separator).map(|field| field.get()).collect()
+    }
+
+    /// Exactly `N` values between each `separator`, see [`split`](Field::split).
+    pub fn array<T: FromField, const N: usize>(&self, separator: &'a str) -> ParseResult<[T; N]> {
+        let values = self.list(separator)?;
+        let found = values.len();
+        values.try_into().map_err(|_| {
+            let field = self.field;
+            self.error(format!("expected {N} values in `{field}`, found {found}"))
+        })
+    }
+
+    /// A key and its whitespace separated values, written `key: 1 2 3`.
+    pub fn key_values<T: FromField>(&self) -> ParseResult<(Self, Vec<T>)> {
+        let (key, values) = self.split_once(":")?;
+        Ok((key.trim(), values.list(" ")?))
+    }
+}
+
+/// A type parsed from a single [`Field`].
+pub trait FromField: Sized {
+    fn from_field(field: Field) -> ParseResult<Self>;
+}
+
+macro_rules! from_str_fields {
+    ($($ty:ty),*) => {
+        $(
+            impl FromField for $ty {
+                fn from_field(field: Field) -> ParseResult<Self> {
+                    field.parse()
+                }
+            }
+        )*
+    };
+}
+
+from_str_fields!(
+    i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize, char, String
+);
+
+/// Written `x,y`.
+impl FromField for Vector {
+    fn from_field(field: Field) -> ParseResult<Self> {
+        let [x, y] = field.array(",")?;
+        Ok(Vector::new(x, y))
+    }
+}
+
+/// Written `x,y,z`.
+impl FromField for Vector3 {
+    fn from_field(field: Field) -> ParseResult<Self> {
+        let [x, y, z] = field.array(",")?;
+        Ok(Vector3::new(x, y, z))
+    }
+}
+
+/// Written `a-b`, including `b`, so `a` can't be negative.
+impl<T: FromField> FromField for RangeInclusive<T> {
+    fn from_field(field: Field) -> ParseResult<Self> {
+        let (start, end) = field.split_once("-")?;
+        Ok(start.get()?..=end.get()?)
+    }
+}
+
+/// Every line of the input.
+pub fn lines(raw: &[Rc<str>]) -> impl Iterator<Item = Field<'_>> {
+    raw.iter()
+        .enumerate()
+        .map(|(index, line)| Field::new(index, line))
+}
+
+/// Parses every line of the input.
+pub fn parse_lines<T: FromField>(raw: &[Rc<str>]) -> ParseResult<Vec<T>> {
+    lines(raw).map(|line| line.get()).collect()
+}
+
+/// A run of lines between blank lines, see [`sections`].
+#[derive(Debug, Clone, Copy)]
+pub struct Section<'a> {
+    /// The index of the section's first line in the input.
+    pub start: usize,
+    pub lines: &'a [Rc<str>],
+}
+
+impl<'a> Section<'a> {
+    pub fn fields(&self) -> impl Iterator<Item = Field<'a>> + use<'a> {
+        let start = self.start;
+        (self.lines.iter().enumerate()).map(move |(index, line)| Field::new(start + index, line))
+    }
+
+    /// The first line, such as `seed-to-soil map:`.
+    pub fn header(&self) -> ParseResult<Field<'a>> {
+        self.fields()
+            .next()
+            .ok_or_else(|| ParseError::on_line(self.start, "empty section"))
+    }
+
+    /// The lines after the header.
+    pub fn body(&self) -> impl Iterator<Item = Field<'a>> + use<'a> {
+        self.fields().skip(1)
+    }
+}
+
+/// The sections of the input, separated by blank lines.
+pub fn sections(raw: &[Rc<str>]) -> Vec<Section<'_>> {
+    let mut sections = vec![];
+    let mut start = 0;
+    for lines in raw.split(|line| line.is_empty()) {
+        if !lines.is_empty() {
+            sections.push(Section { start, lines });
+        }
+        start += lines.len() + 1;
+    }
+    sections
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::split;
+
+    #[test]
+    fn list() {
+        let field = Field::new(0, "3   4  5");
+        assert_eq!(field.list::<i64>(" "), Ok(vec![3, 4, 5]));
+        let field = Field::new(0, "1,-2");
+        assert_eq!(field.list::<i64>(","), Ok(vec![1, -2]));
+    }
+
+    #[test]
+    fn list_error_column() {
+        let error = Field::new(4, "1,2,x").list::<i64>(",").unwrap_err();
+        assert_eq!((error.line, error.column), (Some(4), Some(4)));
+        assert_eq!(error.message, "invalid `x`: invalid digit found in string");
+    }
+
+    #[test]
+    fn ranges() {
+        let field = Field::new(0, "11-22,95-115");
+        assert_eq!(
+            field.list::<RangeInclusive<i64>>(","),
+            Ok(vec![11..=22, 95..=115])
+        );
+        let error = Field::new(0, "11-22,95").list::<RangeInclusive<i64>>(",");
+        let error = error.unwrap_err();
+        assert_eq!(error.column, Some(6));
+        assert_eq!(error.message, "missing `-` in `95`");
+    }
+
+    #[test]
+    fn vectors() {
+        assert_eq!(Field::new(0, "3,-4").get(), Ok(Vector::new(3, -4)));
+        assert_eq!(Field::new(0, "1,2,3").get(), Ok(Vector3::new(1, 2, 3)));
+        let error = Field::new(0, "1,2").get::<Vector3>().unwrap_err();
+        assert_eq!(error.message, "expected 3 values in `1,2`, found 2");
+    }
+
+    #[test]
+    fn key_values() {
+        let (key, values) = Field::new(0, "seeds: 79 14 55")
+            .key_values::<i64>()
+            .unwrap();
+        assert_eq!(key.as_str(), "seeds");
+        assert_eq!(values, [79, 14, 55]);
+        let error = Field::new(0, "seeds 79").key_values::<i64>().unwrap_err();
+        assert_eq!(error.message, "missing `:` in `seeds 79`");
+    }
+
+    #[test]
+    fn strip_prefix() {
+        let field = Field::new(0, "Game 12");
+        assert_eq!(field.strip_prefix("Game ").unwrap().get(), Ok(12));
+        let error = field.strip_prefix("Card ").unwrap_err();
+        assert_eq!(error.message, "expected `Card ` at `Game 12`");
+    }
+
+    #[test]
+    fn sections_and_lines() {
+        let raw = split("seeds: 1 2\n\na map:\n1 2 3\n4 5 6\n\n\nb map:\n7 8 9".into());
+        let sections = sections(&raw);
+        assert_eq!(sections.len(), 3);
+        assert_eq!(sections[1].start, 2);
+        assert_eq!(sections[1].header().unwrap().as_str(), "a map:");
+        let body = sections[1].body().collect::<Vec<_>>();
+        assert_eq!(body[1].line(), 4);
+        assert_eq!(body[1].array::<i64, 3>(" "), Ok([4, 5, 6]));
+        assert_eq!(sections[2].start, 7);
+
+        let raw = split("1,2\n3,x".into());
+        let error = parse_lines::<Vector>(&raw).unwrap_err();
+        assert_eq!((error.line, error.column), (Some(1), Some(2)));
+    }
+}
@@ -1,2 +1,3 @@
-pub use crate::solution::{Meta, Solution};
+pub use crate::error::{Context, ParseError, ParseResult, parse_field};
+pub use crate::solution::{Meta, Sample, Solution};
 pub use std::rc::Rc;
@@ -0,0 +1,289 @@
+//! Shortest path searches over positions such as [`Vector`] and [`Vector3`], given a function
+//! listing each node's neighbours:
+//!
+//! ```ignore
+//! let search = search::bfs(start, |pos| DIRS.map(|dir| pos + dir).into_iter().filter(open));
+//! let steps = search.distance(goal)?;
+//! let path = search.path(goal)?;
+//! ```
+
+use std::{
+    collections::{BinaryHeap, HashMap, HashSet, VecDeque},
+    hash::Hash,
+};
+
+use crate::{vector::Vector, vector3::Vector3};
+
+/// The result of a search: how far the start is from each node it reached and how.
+#[derive(Debug, Clone)]
+pub struct Search<N> {
+    /// The fewest steps, or lowest cost, from the start to each reached node.
+    pub distances: HashMap<N, i64>,
+    /// The node each reached node was reached from, on a shortest path.
+    pub predecessors: HashMap<N, N>,
+}
+
+impl<N: Copy + Eq + Hash> Search<N> {
+    fn new(start: N) -> Self {
+        Self {
+            distances: HashMap::from([(start, 0)]),
+            predecessors: HashMap::new(),
+        }
+    }
+
+    pub fn distance(&self, node: N) -> Option<i64> {
+        self.distances.get(&node).copied()
+    }
+
+    /// The nodes on a shortest path from the start to `goal`, both included.
+    pub fn path(&self, goal: N) -> Option<Vec<N>> {
+        self.distances.get(&goal)?;
+        let mut path = vec![goal];
+        while let Some(&previous) = self.predecessors.get(path.last()?) {
+            path.push(previous);
+        }
+        path.reverse();
+        Some(path)
+    }
+
+    /// Records reaching `next` from `node` at `distance`, if that's closer than before.
+    fn relax(&mut self, node: N, next: N, distance: i64) -> bool {
+        match self.distances.get(&next) {
+            Some(&known) if known <= distance => false,
+            _ => {
+                self.distances.insert(next, distance);
+                self.predecessors.insert(next, node);
+                true
+            }
+        }
+    }
+}
+
+/// Breadth first search from `start`, where every step costs one.
+pub fn bfs<N, I>(start: N, mut neighbors: impl FnMut(N) -> I) -> Search<N>
+where
+    N: Copy + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut search = Search::new(start);
+    let mut queue = VecDeque::from([(start, 0)]);
+    while let Some((node, distance)) = queue.pop_front() {
+        for next in neighbors(node) {
+            if !search.distances.contains_key(&next) {
+                search.relax(node, next, distance + 1);
+                queue.push_back((next, distance + 1));
+            }
+        }
+    }
+    search
+}
+
+/// Dijkstra's search from `start`, where `neighbors` gives each step's non-negative cost.
+pub fn dijkstra<N, I>(start: N, neighbors: impl FnMut(N) -> I) -> Search<N>
+where
+    N: Copy + Eq + Hash,
+    I: IntoIterator<Item = (N, i64)>,
+{
+    best_first(start, neighbors, |_| 0, |_| false)
+}
+
+/// A* search from `start` to `goal`, guided by the [`Manhattan`] distance to `goal`, so each
+/// step has to cost at least its length. Stops once `goal` is reached, so only the distances of
+/// the nodes on its path are final.
+pub fn astar<N, I>(start: N, goal: N, neighbors: impl FnMut(N) -> I) -> Search<N>
+where
+    N: Copy + Eq + Hash + Manhattan,
+    I: IntoIterator<Item = (N, i64)>,
+{
+    best_first(
+        start,
+        neighbors,
+        |node| node.manhattan(goal),
+        |node| node == goal,
+    )
+}
+
+fn best_first<N, I>(
+    start: N,
+    mut neighbors: impl FnMut(N) -> I,
+    heuristic: impl Fn(N) -> i64,
+    done: impl Fn(N) -> bool,
+) -> Search<N>
+where
+    N: Copy + Eq + Hash,
+    I: IntoIterator<Item = (N, i64)>,
+{
+    let mut search = Search::new(start);
+    let mut heap = BinaryHeap::from([State {
+        node: start,
+        estimate: heuristic(start),
+        distance: 0,
+    }]);
+    while let Some(State { node, distance, .. }) = heap.pop() {
+        if search.distance(node) < Some(distance) {
+            // Reached more cheaply since this was queued.
+            continue;
+        }
+        if done(node) {
+            break;
+        }
+        for (next, cost) in neighbors(node) {
+            let distance = distance + cost;
+            if search.relax(node, next, distance) {
+                heap.push(State {
+                    node: next,
+                    estimate: distance + heuristic(next),
+                    distance,
+                });
+            }
+        }
+    }
+    search
+}
+
+/// Every node reachable from `start`, including it.
+pub fn flood_fill<N, I>(start: N, mut neighbors: impl FnMut(N) -> I) -> HashSet<N>
+where
+    N: Copy + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut reached = HashSet::from([start]);
+    let mut stack = vec![start];
+    while let Some(node) = stack.pop() {
+        for next in neighbors(node) {
+            if reached.insert(next) {
+                stack.push(next);
+            }
+        }
+    }
+    reached
+}
+
+/// The distance between two positions moving along the axes, for [`astar`].
+pub trait Manhattan {
+    fn manhattan(self, other: Self) -> i64;
+}
+
+impl Manhattan for Vector {
+    fn manhattan(self, other: Self) -> i64 {
+        (self - other).abs().sum()
+    }
+}
+
+impl Manhattan for Vector3 {
+    fn manhattan(self, other: Self) -> i64 {
+        (self.x - other.x).abs() + (self.y - other.y).abs() + (self.z - other.z).abs()
+    }
+}
+
+#[derive(Eq, PartialEq)]
+struct State<N> {
+    node: N,
+    /// The distance so far plus the heuristic's guess of the rest.
+    estimate: i64,
+    distance: i64,
+}
+
+impl<N: Eq> Ord for State<N> {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.estimate.cmp(&other.estimate).reverse()
+    }
+}
+
+impl<N: Eq> PartialOrd for State<N> {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{direction::DIRS, solution::split, vector::VectorSet};
+
+    /// ```text
+    /// S....
+    /// ###.#
+    /// .....
+    /// .#.#.
+    /// ...#E
+    /// ```
+    fn maze() -> (VectorSet, Vector, Vector) {
+        let raw = split("S....\n###.#\n.....\n.#.#.\n...#E".into());
+        let walls = VectorSet::from_grid(&raw, '#');
+        (walls, Vector::new(0, 0), Vector::new(4, 4))
+    }
+
+    fn open(walls: &VectorSet) -> impl Fn(Vector) -> Vec<Vector> + '_ {
+        move |pos| {
+            DIRS.map(|dir| pos + dir)
+                .into_iter()
+                .filter(|&next| next.contained_in(Vector::zero(), walls.size()))
+                .filter(|&next| !walls.contains(next))
+                .collect()
+        }
+    }
+
+    #[test]
+    fn bfs_path() {
+        let (walls, start, goal) = maze();
+        let search = bfs(start, open(&walls));
+        assert_eq!(search.distance(goal), Some(8));
+        let path = search.path(goal).unwrap();
+        assert_eq!(path.len(), 9);
+        assert_eq!((path[0], path[8]), (start, goal));
+        assert!(path.windows(2).all(|step| step[0].manhattan(step[1]) == 1));
+        assert_eq!(search.path(Vector::new(4, 1)), None);
+        assert_eq!(search.path(start), Some(vec![start]));
+    }
+
+    #[test]
+    fn dijkstra_costs() {
+        // Steps down cost 10, the others 1.
+        let neighbors = |pos: Vector| {
+            DIRS.map(|dir| pos + dir)
+                .into_iter()
+                .filter(|next| next.contained_in(Vector::zero(), Vector::new(3, 3)))
+                .map(move |next| (next, if next.y > pos.y { 10 } else { 1 }))
+        };
+        let search = dijkstra(Vector::zero(), neighbors);
+        assert_eq!(search.distance(Vector::new(2, 2)), Some(22));
+        assert_eq!(search.distance(Vector::new(0, 2)), Some(20));
+        assert_eq!(search.distances.len(), 9);
+    }
+
+    #[test]
+    fn astar_matches_bfs() {
+        let (walls, start, goal) = maze();
+        let open = open(&walls);
+        let search = astar(start, goal, |pos| {
+            open(pos).into_iter().map(|next| (next, 1))
+        });
+        assert_eq!(search.distance(goal), Some(8));
+        assert_eq!(search.path(goal).unwrap().len(), 9);
+    }
+
+    #[test]
+    fn astar_3d() {
+        let goal = Vector3::new(3, 2, 1);
+        let neighbors = |pos: Vector3| {
+            let steps = [
+                Vector3::new(1, 0, 0),
+                Vector3::new(0, 1, 0),
+                Vector3::new(0, 0, 1),
+            ];
+            steps.map(|step| (pos + step, 1))
+        };
+        let search = astar(Vector3::zero(), goal, neighbors);
+        assert_eq!(search.distance(goal), Some(6));
+        assert_eq!(search.path(goal).unwrap().len(), 7);
+    }
+
+    #[test]
+    fn flood() {
+        let (walls, start, _) = maze();
+        let reached = flood_fill(start, open(&walls));
+        assert_eq!(reached.len(), 18);
+        assert!(!reached.contains(&Vector::new(4, 1)));
+    }
+}
@@ -1,93 +1,362 @@
-use std::rc::Rc;
+use std::{fmt::Display, rc::Rc, time::Duration};
 
-pub type Run = Option<(
-    std::time::Duration,
-    std::time::Duration,
-    std::time::Duration,
-)>;
+use crate::{
+    answers::{Recorded, Verification},
+    error::{ParseError, ParseResult},
+    input::Input,
+    memory::{Memory, measure},
+    trace::Frames,
+};
 
-pub trait Solution<T: std::fmt::Display + Eq, U: std::fmt::Display + Eq> {
-    fn meta() -> Meta<T, U>
+/// A day's solution, optionally configured by puzzle parameters `P`.
+///
+/// Parameters cover values the puzzle text states rather than the input, such as a step count.
+/// `P::default()` holds the values for the real input, while each sample can carry its own.
+pub trait Solution<T: Display + Eq, U: Display + Eq, P: Clone + Default = ()> {
+    fn meta() -> Meta<T, U, P>
     where
         Self: Sized;
-    fn new(raw: Vec<Rc<str>>) -> Self
+    fn new(raw: Vec<Rc<str>>) -> ParseResult<Self>
     where
         Self: Sized;
-    fn part_a(&self) -> Option<T>;
-    fn part_b(&self) -> Option<U>;
-    fn run(silenced: bool) -> Run
+    /// Parses the input with the given parameters, solutions with parameters should override this.
+    fn new_with(raw: Vec<Rc<str>>, _params: &P) -> ParseResult<Self>
     where
         Self: Sized,
     {
-        let meta = Self::meta();
-        let start = std::time::Instant::now();
-        let real = Self::new(split(meta.input));
-        let duration = start.elapsed();
+        Self::new(raw)
+    }
+    fn part_a(&self) -> Option<T>;
+    fn part_b(&self) -> Option<U>;
+}
 
-        if silenced {
-            let start = std::time::Instant::now();
-            real.part_a();
-            let a = start.elapsed();
-            let start = std::time::Instant::now();
-            real.part_b();
-            let b = start.elapsed();
-            return Some((duration, a, b));
+pub fn split(input: String) -> Vec<Rc<str>> {
+    input.trim_end().split('\n').map(Rc::from).collect()
+}
+
+/// Runtime configuration for running a single day.
+#[derive(Debug, Clone, Default)]
+pub struct Options {
+    pub year: usize,
+    pub day: usize,
+    pub input: Input,
+    pub mode: Mode,
+    /// Runs only this part, `'a'` or `'b'`, instead of both.
+    pub part: Option<char>,
+    /// Limits each phase, from parsing to solving a part, see [`run`](crate::dynamic::run).
+    pub timeout: Option<Duration>,
+    /// The day's confirmed answers to verify the real answers against.
+    pub answers: Recorded,
+    /// Captures the frames the solution emits, see [`trace`](crate::trace).
+    pub trace: bool,
+}
+
+impl Options {
+    /// The parts to run, in order.
+    pub fn parts(&self) -> Vec<char> {
+        match self.part {
+            Some(part) => vec![part],
+            None => vec!['a', 'b'],
         }
+    }
+}
 
-        let sample_a = Self::new(split(meta.sample_a));
-        let sample_b = Self::new(split(meta.sample_b));
-        println!("parse :\t{duration:?}\n");
-        match sample_a.part_a() {
-            Some(received) if received == meta.answer_a => {
-                let start = std::time::Instant::now();
-                let answer = real.part_a();
-                let duration = start.elapsed();
-                println!("part_a:\t{}", answer.unwrap());
-                println!(" ** in:\t{duration:?}");
-            }
-            Some(received) => {
-                println!("part_a: failed!");
-                println!("\texpected: {}", meta.answer_a);
-                println!("\treceived: {received}");
+/// Which inputs a day is run on.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Mode {
+    /// Checks the samples, then solves the real input for the parts whose samples passed.
+    #[default]
+    Checked,
+    /// Only solves the samples, reporting every one of them.
+    Samples,
+    /// Only solves the real input, without checking the samples, e.g. while benchmarking.
+    Real,
+}
+
+#[derive(Clone)]
+pub struct Meta<T, U, P = ()> {
+    pub input: String,
+    pub samples_a: Vec<Sample<T, P>>,
+    pub samples_b: Vec<Sample<U, P>>,
+}
+
+/// A named worked example for one part, with the answer it should produce.
+#[derive(Clone)]
+pub struct Sample<T, P = ()> {
+    pub name: &'static str,
+    pub input: String,
+    pub answer: T,
+    pub params: P,
+}
+
+impl<T, P: Default> Sample<T, P> {
+    pub fn new(name: &'static str, input: &str, answer: T) -> Self {
+        Self {
+            name,
+            input: input.to_string(),
+            answer,
+            params: P::default(),
+        }
+    }
+
+    /// Replaces the real input's parameters used by this sample.
+    pub fn with_params(self, params: P) -> Self {
+        Self { params, ..self }
+    }
+}
+
+/// The outcome of checking a part against all of its samples.
+#[derive(Debug, Clone, Default)]
+pub enum Check {
+    Passed,
+    Failed {
+        name: &'static str,
+        expected: String,
+        received: String,
+    },
+    Unsolved,
+    Invalid {
+        name: &'static str,
+        error: ParseError,
+    },
+    /// The samples didn't finish within the time limit.
+    TimedOut,
+    /// The samples were not run, e.g. while benchmarking.
+    #[default]
+    Skipped,
+}
+
+impl Check {
+    /// A short, stable name for the outcome, for machine-readable output.
+    pub fn label(&self) -> &'static str {
+        match self {
+            Check::Passed => "passed",
+            Check::Failed { .. } => "failed",
+            Check::Unsolved => "unsolved",
+            Check::TimedOut => "timeout",
+            Check::Invalid { .. } => "invalid",
+            Check::Skipped => "skipped",
+        }
+    }
+}
+
+/// Runs every sample through `solve`, stopping at the first one that doesn't match.
+pub fn check<T: Display + Eq, P>(
+    samples: &[Sample<T, P>],
+    solve: impl Fn(Vec<Rc<str>>, &P) -> ParseResult<Option<T>>,
+) -> Check {
+    for sample in samples {
+        match solve(split(sample.input.clone()), &sample.params) {
+            Ok(Some(received)) if received == sample.answer => {}
+            Ok(Some(received)) => {
+                return Check::Failed {
+                    name: sample.name,
+                    expected: sample.answer.to_string(),
+                    received: received.to_string(),
+                };
             }
-            None => {
-                println!("part_a: unsolved!");
+            Ok(None) => return Check::Unsolved,
+            Err(error) => {
+                return Check::Invalid {
+                    name: sample.name,
+                    error,
+                };
             }
         }
+    }
 
-        match sample_b.part_b() {
-            Some(received) if received == meta.answer_b => {
-                let start = std::time::Instant::now();
-                let answer = real.part_b();
-                let duration = start.elapsed();
-                println!("part_b:\t{}", answer.unwrap());
-                println!(" ** in:\t{duration:?}");
-            }
-            Some(received) => {
-                println!("part_b: failed!");
-                println!("\texpected: {}", meta.answer_b);
-                println!("\treceived: {received}");
+    Check::Passed
+}
+
+/// The outcome of solving a single sample, see [`run_samples`].
+#[derive(Debug, Clone)]
+pub struct SampleRun {
+    pub name: &'static str,
+    pub expected: String,
+    pub received: ParseResult<Option<String>>,
+    pub time: Duration,
+}
+
+impl SampleRun {
+    pub fn passed(&self) -> bool {
+        matches!(&self.received, Ok(Some(received)) if *received == self.expected)
+    }
+}
+
+/// Runs every sample through `solve`, unlike [`check`] carrying on past failures.
+pub fn run_samples<T: Display + Eq, P>(
+    samples: &[Sample<T, P>],
+    solve: impl Fn(Vec<Rc<str>>, &P) -> ParseResult<Option<T>>,
+) -> Vec<SampleRun> {
+    samples
+        .iter()
+        .map(|sample| {
+            let start = std::time::Instant::now();
+            let received = solve(split(sample.input.clone()), &sample.params);
+            SampleRun {
+                name: sample.name,
+                expected: sample.answer.to_string(),
+                received: received.map(|received| received.map(|received| received.to_string())),
+                time: start.elapsed(),
             }
-            None => {
-                println!("part_b: unsolved!");
+        })
+        .collect()
+}
+
+impl Check {
+    /// The outcome of the first sample that didn't pass, as [`check`] would report it.
+    pub fn of(runs: &[SampleRun]) -> Check {
+        let Some(run) = runs.iter().find(|run| !run.passed()) else {
+            return Check::Passed;
+        };
+        match &run.received {
+            Ok(Some(received)) => Check::Failed {
+                name: run.name,
+                expected: run.expected.clone(),
+                received: received.clone(),
+            },
+            Ok(None) => Check::Unsolved,
+            Err(error) => Check::Invalid {
+                name: run.name,
+                error: error.clone(),
+            },
+        }
+    }
+}
+
+/// Why a day could not be run at all.
+#[derive(Debug, Clone)]
+pub enum Failure {
+    Input(String),
+    Parse(ParseError),
+    Panicked,
+    /// Parsing didn't finish within the time limit.
+    TimedOut(Duration),
+}
+
+impl Display for Failure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Failure::Input(err) => write!(f, "failed to load input: {err}"),
+            Failure::Parse(err) => write!(f, "failed to parse input: {err}"),
+            Failure::Panicked => write!(f, "panicked"),
+            Failure::TimedOut(limit) => write!(f, "parsing timed out after {limit:?}"),
+        }
+    }
+}
+
+/// Everything learned from running a single day, for printing in any format.
+#[derive(Debug, Clone, Default)]
+pub struct Report {
+    pub year: usize,
+    pub day: usize,
+    /// Time taken to parse the real input.
+    pub parse: Option<Duration>,
+    /// Heap used to parse the real input, when the counting allocator is installed.
+    pub parse_memory: Option<Memory>,
+    /// The [`fingerprint`] of the real input.
+    pub input: Option<String>,
+    pub failure: Option<Failure>,
+    pub part_a: Part,
+    pub part_b: Part,
+    /// The frames captured in each phase, e.g. `"samples a"` or `"b"`, that emitted any.
+    pub trace: Vec<(&'static str, Frames)>,
+}
+
+impl Report {
+    pub fn new(year: usize, day: usize) -> Self {
+        Self {
+            year,
+            day,
+            ..Default::default()
+        }
+    }
+
+    pub fn failed(year: usize, day: usize, failure: Failure) -> Self {
+        Self {
+            failure: Some(failure),
+            ..Self::new(year, day)
+        }
+    }
+
+    /// Verifies the parts that ran on the real input, leaving the others unverified.
+    pub(crate) fn verified(mut self, answers: &Recorded) -> Self {
+        if let Some(input) = &self.input {
+            let parts = [('a', &mut self.part_a), ('b', &mut self.part_b)];
+            for (part, result) in parts
+                .into_iter()
+                .filter(|(_, result)| result.time.is_some())
+            {
+                result.verification = answers.verify(input, part, result.answer.as_deref());
             }
         }
+        self
+    }
 
-        None
+    pub fn part(&self, part: char) -> &Part {
+        match part {
+            'a' => &self.part_a,
+            _ => &self.part_b,
+        }
+    }
+
+    pub(crate) fn part_mut(&mut self, part: char) -> &mut Part {
+        match part {
+            'a' => &mut self.part_a,
+            _ => &mut self.part_b,
+        }
+    }
+
+    pub fn parts(&self) -> [(char, &Part); 2] {
+        [('a', &self.part_a), ('b', &self.part_b)]
+    }
+
+    /// Heap used by parsing and both parts, if all of them were measured.
+    pub fn memory(&self) -> Option<Memory> {
+        Some(
+            self.parse_memory?
+                .then(self.part_a.memory?)
+                .then(self.part_b.memory?),
+        )
     }
 }
 
-pub fn split(input: String) -> Vec<Rc<str>> {
-    input.trim_end().split('\n').map(Rc::from).collect()
+/// The outcome of one part: its samples, and the real answer if they passed.
+#[derive(Debug, Clone, Default)]
+pub struct Part {
+    pub samples: Check,
+    pub answer: Option<String>,
+    pub time: Option<Duration>,
+    /// Heap used to solve the real input and render the answer, when the counting allocator is
+    /// installed.
+    pub memory: Option<Memory>,
+    /// Solving the real input didn't finish within the time limit.
+    pub timed_out: bool,
+    /// Every sample's outcome, when only the samples were run.
+    pub sample_runs: Vec<SampleRun>,
+    /// Whether the answer matches the recorded one.
+    pub verification: Verification,
 }
 
-#[derive(Clone)]
-pub struct Meta<T, U> {
-    pub input: String,
-    pub sample_a: String,
-    pub sample_b: String,
-    pub answer_a: T,
-    pub answer_b: U,
+impl Part {
+    /// Whether the part wasn't run at all, e.g. when another part was selected.
+    pub fn skipped(&self) -> bool {
+        matches!(self.samples, Check::Skipped) && self.time.is_none() && !self.timed_out
+    }
+
+    pub(crate) fn timed<T: Display>(samples: Check, solve: impl FnOnce() -> Option<T>) -> Self {
+        let ((answer, time), memory) = measure(|| {
+            let start = std::time::Instant::now();
+            (solve(), start.elapsed())
+        });
+        Self {
+            samples,
+            answer: answer.map(|answer| answer.to_string()),
+            time: Some(time),
+            memory,
+            ..Default::default()
+        }
+    }
 }
 
 #[macro_export]
@@ -101,16 +370,32 @@ macro_rules! test_solution {
 
                 #[test]
                 fn part_a() {
-                    let meta = Day::meta();
-                    let solution = Day::new($crate::solution::split(meta.sample_a));
-                    assert_eq!(solution.part_a(), Some(meta.answer_a));
+                    for sample in Day::meta().samples_a {
+                        let raw = $crate::solution::split(sample.input);
+                        let solution = Day::new_with(raw, &sample.params)
+                            .unwrap_or_else(|err| panic!("sample `{}`: {err}", sample.name));
+                        assert_eq!(
+                            solution.part_a(),
+                            Some(sample.answer),
+                            "sample `{}`",
+                            sample.name
+                        );
+                    }
                 }
 
                 #[test]
                 fn part_b() {
-                    let meta = Day::meta();
-                    let solution = Day::new($crate::solution::split(meta.sample_b));
-                    assert_eq!(solution.part_b(), Some(meta.answer_b));
+                    for sample in Day::meta().samples_b {
+                        let raw = $crate::solution::split(sample.input);
+                        let solution = Day::new_with(raw, &sample.params)
+                            .unwrap_or_else(|err| panic!("sample `{}`: {err}", sample.name));
+                        assert_eq!(
+                            solution.part_b(),
+                            Some(sample.answer),
+                            "sample `{}`",
+                            sample.name
+                        );
+                    }
                 }
             }
         }
@@ -0,0 +1,228 @@
+//! Frames that solutions emit to show how they work, e.g. a search's grid and current path.
+//!
+//! Emitting does nothing unless the runner is capturing frames on the current thread, and the
+//! frame is only built when it is, so solutions can leave their [`emit`] calls in place.
+
+use std::{
+    cell::RefCell,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use crate::vector::{Grid, Render, Vector, VectorSet};
+
+/// The most frames kept per capture, later ones are counted but dropped.
+pub const LIMIT: usize = 10_000;
+
+/// A snapshot of a solution's state: a grid of characters, highlighted cells and counters.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Frame {
+    pub label: String,
+    pub rows: Vec<String>,
+    /// The position of the first row's first cell, which highlights are relative to.
+    pub origin: Vector,
+    /// Cells to draw highlighted, such as the current path.
+    pub highlights: Vec<Vector>,
+    pub counters: Vec<(&'static str, i64)>,
+}
+
+impl Frame {
+    pub fn new(label: impl Into<String>) -> Self {
+        Self {
+            label: label.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Draws a grid of `size` cell by cell.
+    pub fn cells(self, size: Vector, draw: impl Fn(Vector) -> char) -> Self {
+        let rows = (0..size.y)
+            .map(|y| (0..size.x).map(|x| draw(Vector::new(x, y))).collect())
+            .collect();
+        Self { rows, ..self }
+    }
+
+    /// Draws a grid's [`bounds`](Grid::bounds), using `empty` for the cells without a value.
+    pub fn map<G: Grid>(self, grid: &G, empty: char, draw: impl Fn(&G::Value) -> char) -> Self {
+        self.render(&grid.render(draw).empty(empty))
+    }
+
+    /// Draws a rendered grid, including its overlays.
+    pub fn render<G: Grid>(self, render: &Render<G>) -> Self {
+        Self {
+            rows: render.rows(),
+            origin: render.origin(),
+            ..self
+        }
+    }
+
+    /// Draws a set as `#` on `.`.
+    pub fn set(self, set: &VectorSet) -> Self {
+        self.cells(set.size(), |pos| match set.contains(pos) {
+            true => '#',
+            false => '.',
+        })
+    }
+
+    pub fn highlight(mut self, cells: impl IntoIterator<Item = Vector>) -> Self {
+        self.highlights.extend(cells);
+        self
+    }
+
+    pub fn counter(mut self, name: &'static str, value: impl TryInto<i64>) -> Self {
+        self.counters
+            .push((name, value.try_into().unwrap_or(i64::MAX)));
+        self
+    }
+}
+
+/// The frames captured from one run, see [`capture`].
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Frames {
+    pub frames: Vec<Frame>,
+    /// Frames emitted past the [`LIMIT`].
+    pub dropped: usize,
+}
+
+impl Frames {
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty() && self.dropped == 0
+    }
+}
+
+thread_local! {
+    static SINK: RefCell<Option<Frames>> = const { RefCell::new(None) };
+}
+
+/// How many threads are capturing, so emitting costs one load when none are.
+static CAPTURING: AtomicUsize = AtomicUsize::new(0);
+
+/// Whether frames emitted on this thread are being captured.
+pub fn enabled() -> bool {
+    CAPTURING.load(Ordering::Relaxed) > 0 && SINK.with_borrow(Option::is_some)
+}
+
+/// Emits the frame built by `frame`, which is only called while capturing.
+#[inline]
+pub fn emit(frame: impl FnOnce() -> Frame) {
+    if CAPTURING.load(Ordering::Relaxed) > 0 {
+        emit_captured(frame);
+    }
+}
+
+fn emit_captured(frame: impl FnOnce() -> Frame) {
+    let room = SINK.with_borrow_mut(|sink| match sink {
+        None => false,
+        Some(sink) if sink.frames.len() >= LIMIT => {
+            sink.dropped += 1;
+            false
+        }
+        Some(_) => true,
+    });
+    if room {
+        // Built outside the borrow, in case building it emits too.
+        let frame = frame();
+        SINK.with_borrow_mut(|sink| {
+            if let Some(sink) = sink {
+                sink.frames.push(frame);
+            }
+        });
+    }
+}
+
+/// Runs `f`, capturing the frames it emits if `enabled`.
+pub fn capture<R>(enabled: bool, f: impl FnOnce() -> R) -> (R, Frames) {
+    if !enabled {
+        return (f(), Frames::default());
+    }
+
+    /// Restores the previous capture even if `f` panics, as runner threads outlive the day.
+    struct Guard(Option<Frames>);
+
+    impl Drop for Guard {
+        fn drop(&mut self) {
+            SINK.set(self.0.take());
+            CAPTURING.fetch_sub(1, Ordering::Relaxed);
+        }
+    }
+
+    CAPTURING.fetch_add(1, Ordering::Relaxed);
+    let _guard = Guard(SINK.replace(Some(Frames::default())));
+    let result = f();
+    let frames = SINK.take().unwrap_or_default();
+    (result, frames)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vector::SparseMap;
+
+    #[test]
+    fn disabled() {
+        let ((), frames) = capture(false, || {
+            assert!(!enabled());
+            emit(|| unreachable!("frames are only built while capturing"));
+        });
+        assert!(frames.is_empty());
+    }
+
+    #[test]
+    fn captures() {
+        let mut set = VectorSet::new(Vector::new(3, 2));
+        set.insert(Vector::new(1, 0));
+        let (answer, frames) = capture(true, || {
+            emit(|| Frame::new("start").set(&set).highlight([Vector::new(0, 1)]));
+            emit(|| Frame::new("end").counter("steps", 2usize));
+            42
+        });
+
+        assert_eq!(answer, 42);
+        assert!(!enabled());
+        assert_eq!(frames.frames.len(), 2);
+        assert_eq!(frames.frames[0].rows, [".#.", "..."]);
+        assert_eq!(frames.frames[0].highlights, [Vector::new(0, 1)]);
+        assert_eq!(frames.frames[1].counters, [("steps", 2)]);
+    }
+
+    #[test]
+    fn other_threads() {
+        let ((), frames) = capture(true, || {
+            std::thread::spawn(|| {
+                assert!(!enabled());
+                emit(|| unreachable!("only the capturing thread builds frames"));
+            })
+            .join()
+            .unwrap();
+            emit(|| Frame::new("captured"));
+        });
+        assert_eq!(frames.frames.len(), 1);
+    }
+
+    #[test]
+    fn sparse_origin() {
+        let mut map = SparseMap::new();
+        map.insert(Vector::new(-1, 2), 'a');
+        map.insert(Vector::new(1, 3), 'b');
+        let frame = Frame::new("sparse").map(&map, '.', |&c| c);
+        assert_eq!(frame.rows, ["a..", "..b"]);
+        assert_eq!(frame.origin, Vector::new(-1, 2));
+    }
+
+    #[test]
+    fn limited() {
+        let ((), frames) = capture(true, || {
+            for _ in 0..LIMIT + 5 {
+                emit(|| Frame::new("step"));
+            }
+        });
+        assert_eq!(frames.frames.len(), LIMIT);
+        assert_eq!(frames.dropped, 5);
+    }
+
+    #[test]
+    fn stops_after_panic() {
+        let result = std::panic::catch_unwind(|| capture(true, || panic!("solution panicked")));
+        assert!(result.is_err());
+        assert!(!enabled());
+    }
+}
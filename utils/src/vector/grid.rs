@@ -0,0 +1,136 @@
+use std::collections::HashMap;
+
+use super::{Render, Vector, VectorMap};
+use crate::{direction::DIRS, search};
+
+/// Values by position, on a bounded [`VectorMap`], an endlessly [`Tiled`] one or a growable
+/// [`SparseMap`](super::SparseMap).
+pub trait Grid {
+    type Value;
+
+    fn get(&self, pos: Vector) -> Option<&Self::Value>;
+
+    /// The corners of the area worth drawing, `min` included and `max` excluded.
+    fn bounds(&self) -> (Vector, Vector);
+
+    fn contains(&self, pos: Vector) -> bool {
+        self.get(pos).is_some()
+    }
+
+    /// The orthogonal neighbours of `pos` that have a value.
+    fn neighbors(&self, pos: Vector) -> impl Iterator<Item = (Vector, &Self::Value)> {
+        DIRS.into_iter().filter_map(move |dir| {
+            let next = pos + dir;
+            Some((next, self.get(next)?))
+        })
+    }
+
+    /// Draws the grid as text with `draw` for each value, see [`Render`] for overlays and more.
+    fn render<'a>(&'a self, draw: impl Fn(&Self::Value) -> char + 'a) -> Render<'a, Self>
+    where
+        Self: Sized,
+    {
+        Render::new(self, draw)
+    }
+
+    /// The fewest steps from `start` to every cell reachable through the cells `open` accepts,
+    /// see [`search::bfs`] for the paths too.
+    ///
+    /// On a [`Tiled`] grid `open` has to bound the search, e.g. by the distance from `start`.
+    fn distances(
+        &self,
+        start: Vector,
+        open: impl Fn(Vector, &Self::Value) -> bool,
+    ) -> HashMap<Vector, i64> {
+        let neighbors = |pos| {
+            self.neighbors(pos)
+                .filter(|&(next, value)| open(next, value))
+                .map(|(next, _)| next)
+        };
+        search::bfs(start, neighbors).distances
+    }
+}
+
+impl<T> Grid for VectorMap<T> {
+    type Value = T;
+
+    fn get(&self, pos: Vector) -> Option<&T> {
+        VectorMap::get(self, pos)
+    }
+
+    fn bounds(&self) -> (Vector, Vector) {
+        (Vector::zero(), self.size())
+    }
+}
+
+/// A [`VectorMap`] repeated endlessly in every direction, see [`VectorMap::tiled`].
+#[derive(Debug, Clone, Copy)]
+pub struct Tiled<'a, T> {
+    map: &'a VectorMap<T>,
+}
+
+impl<T> VectorMap<T> {
+    pub fn tiled(&self) -> Tiled<'_, T> {
+        Tiled { map: self }
+    }
+}
+
+impl<T> Grid for Tiled<'_, T> {
+    type Value = T;
+
+    fn get(&self, pos: Vector) -> Option<&T> {
+        self.map.get(pos.rem_euclid(self.map.size()))
+    }
+
+    /// The original tile.
+    fn bounds(&self) -> (Vector, Vector) {
+        self.map.bounds()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::solution::split;
+
+    fn walls(grid: &str) -> VectorMap<bool> {
+        let raw = split(grid.into());
+        VectorMap::from_grid_with(&raw, &[], |c| Some(c == '#'))
+            .unwrap()
+            .0
+    }
+
+    #[test]
+    fn neighbors() {
+        let map = walls("..\n.#");
+        let neighbors = map.neighbors(Vector::new(0, 1)).collect::<Vec<_>>();
+        assert_eq!(
+            neighbors,
+            [(Vector::new(0, 0), &false), (Vector::new(1, 1), &true)]
+        );
+    }
+
+    #[test]
+    fn distances() {
+        let map = walls("...\n##.\n...");
+        let distances = map.distances(Vector::new(0, 0), |_, &wall| !wall);
+        assert_eq!(distances.len(), 7);
+        assert_eq!(distances[&Vector::new(0, 2)], 6);
+        assert!(!distances.contains_key(&Vector::new(0, 1)));
+    }
+
+    #[test]
+    fn tiled() {
+        let map = walls("#.\n..");
+        let tiled = map.tiled();
+        assert_eq!(tiled.get(Vector::new(-2, 4)), Some(&true));
+        assert_eq!(tiled.get(Vector::new(3, -1)), Some(&false));
+        assert_eq!(tiled.bounds(), (Vector::zero(), Vector::new(2, 2)));
+
+        let start = Vector::new(1, 1);
+        let near = |pos: Vector| (pos - start).abs().sum() <= 3;
+        let distances = tiled.distances(start, |pos, &wall| !wall && near(pos));
+        assert_eq!(distances[&Vector::new(-1, 0)], 3);
+        assert!(!distances.contains_key(&Vector::new(2, 2)));
+    }
+}
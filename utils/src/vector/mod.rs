@@ -1,7 +1,16 @@
+mod grid;
+mod render;
+mod sparsemap;
 mod vectormap;
 mod vectorset;
 #[allow(unused_imports)]
-pub use vectormap::VectorMap;
+pub use grid::{Grid, Tiled};
+#[allow(unused_imports)]
+pub use render::{Render, assert_grid, diff};
+#[allow(unused_imports)]
+pub use sparsemap::SparseMap;
+#[allow(unused_imports)]
+pub use vectormap::{Markers, VectorMap};
 #[allow(unused_imports)]
 pub use vectorset::VectorSet;
 
@@ -0,0 +1,217 @@
+use std::{
+    collections::HashMap,
+    fmt::{self, Display},
+};
+
+use super::{Grid, Vector};
+
+type Draw<'a, T> = Box<dyn Fn(&T) -> char + 'a>;
+
+/// A grid drawn as text, one character per cell, see [`Grid::render`].
+pub struct Render<'a, G: Grid> {
+    grid: &'a G,
+    draw: Draw<'a, G::Value>,
+    empty: char,
+    overlays: HashMap<Vector, char>,
+    min: Vector,
+    max: Vector,
+}
+
+impl<'a, G: Grid> Render<'a, G> {
+    /// Draws each value of the grid's [`bounds`](Grid::bounds) with `draw`.
+    pub fn new(grid: &'a G, draw: impl Fn(&G::Value) -> char + 'a) -> Self {
+        let (min, max) = grid.bounds();
+        Self {
+            grid,
+            draw: Box::new(draw),
+            empty: ' ',
+            overlays: HashMap::new(),
+            min,
+            max,
+        }
+    }
+
+    /// The character for cells without a value, a space by default.
+    pub fn empty(self, empty: char) -> Self {
+        Self { empty, ..self }
+    }
+
+    /// Draws `cells` as `symbol` over the grid and the earlier overlays, e.g. a path or a set.
+    pub fn overlay(mut self, symbol: char, cells: impl IntoIterator<Item = Vector>) -> Self {
+        self.overlays
+            .extend(cells.into_iter().map(|pos| (pos, symbol)));
+        self
+    }
+
+    /// Draws the cells from `min` to `max` instead, `min` included and `max` excluded.
+    pub fn crop(self, min: Vector, max: Vector) -> Self {
+        Self { min, max, ..self }
+    }
+
+    /// The position of the first row's first cell.
+    pub fn origin(&self) -> Vector {
+        self.min
+    }
+
+    pub fn cell(&self, pos: Vector) -> char {
+        match self.overlays.get(&pos) {
+            Some(&symbol) => symbol,
+            None => self.grid.get(pos).map_or(self.empty, &self.draw),
+        }
+    }
+
+    pub fn rows(&self) -> Vec<String> {
+        (self.min.y..self.max.y)
+            .map(|y| {
+                (self.min.x..self.max.x)
+                    .map(|x| self.cell(Vector::new(x, y)))
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+impl<G: Grid> Display for Render<'_, G> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.rows() {
+            writeln!(f, "{row}")?;
+        }
+        Ok(())
+    }
+}
+
+/// The rows of two drawn grids side by side, with differing rows marked `!`, or `None` if
+/// they're the same. Trailing newlines are ignored.
+pub fn diff(expected: &str, actual: &str) -> Option<String> {
+    let expected = expected.lines().collect::<Vec<_>>();
+    let actual = actual.lines().collect::<Vec<_>>();
+    let width = expected.iter().map(|row| row.chars().count()).max();
+    let width = width.unwrap_or_default().max("expected".len());
+
+    let mut lines = vec![format!("  {:width$} | actual", "expected")];
+    let mut differences = vec![];
+    for y in 0..expected.len().max(actual.len()) {
+        let left = expected.get(y).copied().unwrap_or_default();
+        let right = actual.get(y).copied().unwrap_or_default();
+        let (mut a, mut b) = (left.chars(), right.chars());
+        for x in 0..left.chars().count().max(right.chars().count()) {
+            if a.next() != b.next() {
+                differences.push(Vector::new_usize(x, y));
+            }
+        }
+        let marker = if left == right { ' ' } else { '!' };
+        lines.push(format!("{marker} {left:width$} | {right}"));
+    }
+
+    let first = differences.first()?;
+    let count = differences.len();
+    lines.push(format!(
+        "{count} cells differ, first at {},{}",
+        first.x, first.y
+    ));
+    Some(lines.join("\n"))
+}
+
+/// Panics with the [`diff`] unless `actual` is drawn like `expected`, for tests.
+#[track_caller]
+pub fn assert_grid(actual: impl Display, expected: &str) {
+    if let Some(diff) = diff(expected, &actual.to_string()) {
+        panic!("grids differ:\n{diff}");
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        solution::split,
+        vector::{SparseMap, VectorMap},
+    };
+
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    enum Tile {
+        Wall,
+        Floor,
+        Slope,
+    }
+
+    impl TryFrom<char> for Tile {
+        type Error = &'static str;
+
+        fn try_from(c: char) -> Result<Self, Self::Error> {
+            match c {
+                '#' => Ok(Tile::Wall),
+                '.' => Ok(Tile::Floor),
+                '>' => Ok(Tile::Slope),
+                _ => Err("expected a tile"),
+            }
+        }
+    }
+
+    impl Tile {
+        fn symbol(&self) -> char {
+            match self {
+                Tile::Wall => '#',
+                Tile::Floor => '.',
+                Tile::Slope => '>',
+            }
+        }
+    }
+
+    const GRID: &str = "#.##\n..>#\n##..";
+
+    fn tiles() -> VectorMap<Tile> {
+        VectorMap::from_grid(&split(GRID.into()), &[]).unwrap().0
+    }
+
+    #[test]
+    fn round_trip() {
+        assert_grid(tiles().render(Tile::symbol), GRID);
+        assert_eq!(
+            tiles().render(Tile::symbol).to_string(),
+            GRID.to_owned() + "\n"
+        );
+    }
+
+    #[test]
+    fn overlays_and_crop() {
+        let map = tiles();
+        let path = [Vector::new(1, 0), Vector::new(1, 1), Vector::new(2, 1)];
+        let render = map
+            .render(Tile::symbol)
+            .overlay('O', path)
+            .overlay('S', [Vector::new(1, 0)])
+            .crop(Vector::new(1, 0), Vector::new(5, 2));
+        assert_eq!(render.origin(), Vector::new(1, 0));
+        assert_grid(render.empty('?'), "S##?\nOO#?");
+    }
+
+    #[test]
+    fn sparse() {
+        let mut map = SparseMap::new();
+        map.insert(Vector::new(-1, -1), 1);
+        map.insert(Vector::new(1, 0), 2);
+        let render = map.render(|&value| char::from_digit(value, 10).unwrap());
+        assert_grid(render.empty('.'), "1..\n..2");
+    }
+
+    #[test]
+    fn diffs() {
+        assert_eq!(diff(GRID, &(GRID.to_owned() + "\n")), None);
+        let diff = diff("#.#\n...", "#.#\n.#\n#").unwrap();
+        let expected = [
+            "  expected | actual",
+            "  #.#      | #.#",
+            "! ...      | .#",
+            "!          | #",
+            "3 cells differ, first at 1,1",
+        ];
+        assert_eq!(diff, expected.join("\n"));
+    }
+
+    #[test]
+    #[should_panic(expected = "1 cells differ, first at 2,0")]
+    fn assert_grid_panics() {
+        assert_grid(tiles().render(Tile::symbol), "#..#\n..>#\n##..");
+    }
+}
@@ -0,0 +1,177 @@
+use std::{
+    collections::HashMap,
+    ops::{Index, IndexMut},
+};
+
+use super::{Grid, Vector};
+
+/// Values anywhere on an unbounded grid, tracking the bounding box of their positions.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SparseMap<T> {
+    cells: HashMap<Vector, T>,
+    /// The corners of the bounding box, `min` included and `max` excluded.
+    min: Vector,
+    max: Vector,
+}
+
+impl<T> Default for SparseMap<T> {
+    fn default() -> Self {
+        Self {
+            cells: HashMap::new(),
+            min: Vector::zero(),
+            max: Vector::zero(),
+        }
+    }
+}
+
+impl<T> SparseMap<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Inserts `value` at `pos`, returning the value it replaced.
+    pub fn insert(&mut self, pos: Vector, value: T) -> Option<T> {
+        match self.cells.is_empty() {
+            true => (self.min, self.max) = (pos, pos + Vector::new(1, 1)),
+            false => {
+                self.min = Vector::new(self.min.x.min(pos.x), self.min.y.min(pos.y));
+                self.max = Vector::new(self.max.x.max(pos.x + 1), self.max.y.max(pos.y + 1));
+            }
+        }
+        self.cells.insert(pos, value)
+    }
+
+    /// Removes the value at `pos`, shrinking the bounding box if it was on its edge.
+    pub fn remove(&mut self, pos: Vector) -> Option<T> {
+        let value = self.cells.remove(&pos)?;
+        let (min, max) = (self.min, self.max - Vector::new(1, 1));
+        if pos.x == min.x || pos.y == min.y || pos.x == max.x || pos.y == max.y {
+            self.shrink();
+        }
+        Some(value)
+    }
+
+    fn shrink(&mut self) {
+        let mut positions = self.cells.keys();
+        let Some(&first) = positions.next() else {
+            (self.min, self.max) = (Vector::zero(), Vector::zero());
+            return;
+        };
+        let (min, max) = positions.fold((first, first), |(min, max), pos| {
+            let min = Vector::new(min.x.min(pos.x), min.y.min(pos.y));
+            let max = Vector::new(max.x.max(pos.x), max.y.max(pos.y));
+            (min, max)
+        });
+        (self.min, self.max) = (min, max + Vector::new(1, 1));
+    }
+
+    pub fn get(&self, pos: Vector) -> Option<&T> {
+        self.cells.get(&pos)
+    }
+
+    pub fn get_mut(&mut self, pos: Vector) -> Option<&mut T> {
+        self.cells.get_mut(&pos)
+    }
+
+    pub fn contains(&self, pos: Vector) -> bool {
+        self.cells.contains_key(&pos)
+    }
+
+    /// The corners of the bounding box, `min` included and `max` excluded, both zero when empty.
+    pub fn bounds(&self) -> (Vector, Vector) {
+        (self.min, self.max)
+    }
+
+    /// The values in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = (Vector, &T)> {
+        self.cells.iter().map(|(&pos, value)| (pos, value))
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (Vector, &mut T)> {
+        self.cells.iter_mut().map(|(&pos, value)| (pos, value))
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+}
+
+impl<T> Grid for SparseMap<T> {
+    type Value = T;
+
+    fn get(&self, pos: Vector) -> Option<&T> {
+        SparseMap::get(self, pos)
+    }
+
+    fn bounds(&self) -> (Vector, Vector) {
+        SparseMap::bounds(self)
+    }
+}
+
+/// Panics if `pos` has no value, see [`SparseMap::get`].
+impl<T> Index<Vector> for SparseMap<T> {
+    type Output = T;
+
+    fn index(&self, pos: Vector) -> &T {
+        self.get(pos)
+            .unwrap_or_else(|| panic!("no value at {pos:?}"))
+    }
+}
+
+impl<T> IndexMut<Vector> for SparseMap<T> {
+    fn index_mut(&mut self, pos: Vector) -> &mut T {
+        self.get_mut(pos)
+            .unwrap_or_else(|| panic!("no value at {pos:?}"))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn bounds_grow() {
+        let mut map = SparseMap::new();
+        assert_eq!(map.bounds(), (Vector::zero(), Vector::zero()));
+        map.insert(Vector::new(-2, 3), 'a');
+        assert_eq!(map.bounds(), (Vector::new(-2, 3), Vector::new(-1, 4)));
+        map.insert(Vector::new(4, -1), 'b');
+        assert_eq!(map.bounds(), (Vector::new(-2, -1), Vector::new(5, 4)));
+        assert_eq!(map.insert(Vector::new(4, -1), 'c'), Some('b'));
+        assert_eq!(map[Vector::new(4, -1)], 'c');
+        assert_eq!(map.len(), 2);
+    }
+
+    #[test]
+    fn bounds_shrink() {
+        let mut map = SparseMap::new();
+        for (x, y) in [(0, 0), (1, 1), (5, 2)] {
+            map.insert(Vector::new(x, y), ());
+        }
+        assert_eq!(map.remove(Vector::new(1, 1)), Some(()));
+        assert_eq!(map.bounds(), (Vector::new(0, 0), Vector::new(6, 3)));
+        map.remove(Vector::new(5, 2));
+        assert_eq!(map.bounds(), (Vector::new(0, 0), Vector::new(1, 1)));
+        map.remove(Vector::new(0, 0));
+        assert!(map.is_empty());
+        assert_eq!(map.bounds(), (Vector::zero(), Vector::zero()));
+        assert_eq!(map.remove(Vector::new(0, 0)), None);
+    }
+
+    #[test]
+    fn neighbors() {
+        let mut map = SparseMap::new();
+        map.insert(Vector::new(-1, 0), 1);
+        map.insert(Vector::new(0, -1), 2);
+        let mut neighbors = map.neighbors(Vector::zero()).collect::<Vec<_>>();
+        neighbors.sort_by_key(|&(_, &value)| value);
+        assert_eq!(
+            neighbors,
+            [(Vector::new(-1, 0), &1), (Vector::new(0, -1), &2)]
+        );
+    }
+}
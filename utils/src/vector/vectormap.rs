@@ -1,173 +1,410 @@
+use std::{
+    collections::BTreeMap,
+    fmt::Display,
+    iter::Enumerate,
+    ops::{Index, IndexMut},
+    rc::Rc,
+    slice, vec,
+};
+
 use super::Vector;
+use crate::error::{ParseError, ParseResult};
 
+/// Values on a grid of `size`, stored row by row in one buffer.
 #[derive(Debug, Clone)]
 pub struct VectorMap<T> {
-    grid: Vec<Vec<Option<T>>>,
+    cells: Vec<Option<T>>,
     size: Vector,
 }
 
-impl<T: Clone> VectorMap<T> {
+impl<T> VectorMap<T> {
     #[allow(dead_code)]
     pub fn new(size: Vector) -> Self {
         Self {
-            grid: vec![vec![None; size.x as usize]; size.y as usize],
+            cells: std::iter::repeat_with(|| None)
+                .take(size.area() as usize)
+                .collect(),
             size,
         }
     }
 
     #[allow(dead_code)]
     pub fn filled_with(size: Vector, generator: fn(Vector) -> Option<T>) -> Self {
-        let mut grid = Vec::new();
-        for y in 0..size.y {
-            let mut row = Vec::new();
-            for x in 0..size.x {
-                row.push(generator(Vector::new(x, y)));
-            }
-            grid.push(row);
-        }
-        Self { grid, size }
+        let cells = (0..size.area())
+            .map(|index| generator(Vector::new(index % size.x, index / size.x)))
+            .collect();
+        Self { cells, size }
     }
 
-    #[allow(dead_code)]
-    pub fn filled_with_value(size: Vector, value: T) -> Self {
-        Self {
-            grid: vec![vec![Some(value); size.x as usize]; size.y as usize],
-            size,
+    /// The index of `pos` in the buffer, if it's on the grid.
+    fn index(&self, pos: Vector) -> Option<usize> {
+        // Negative coordinates wrap around to beyond the size.
+        let (x, y, width) = (pos.x as usize, pos.y as usize, self.size.x as usize);
+        if x >= width || y >= self.size.y as usize {
+            return None;
         }
+
+        Some(y * width + x)
     }
 
     #[allow(dead_code)]
     pub fn insert(&mut self, pos: Vector, value: T) -> Option<bool> {
-        if !pos.contained_in(Vector::zero(), self.size) {
-            return None;
-        }
-
-        let exists = self.grid[pos.y as usize][pos.x as usize].is_some();
-        self.grid[pos.y as usize][pos.x as usize] = Some(value);
-        Some(!exists)
+        let index = self.index(pos)?;
+        Some(self.cells[index].replace(value).is_none())
     }
 
     #[allow(dead_code)]
     pub fn remove(&mut self, pos: Vector) -> Option<T> {
-        if !pos.contained_in(Vector::zero(), self.size) {
-            return None;
-        }
-
-        let value = self.grid[pos.y as usize][pos.x as usize].clone();
-        self.grid[pos.y as usize][pos.x as usize] = None;
-        value
+        let index = self.index(pos)?;
+        self.cells[index].take()
     }
 
     #[allow(dead_code)]
-    pub fn get(&self, pos: Vector) -> Option<T> {
-        if !pos.contained_in(Vector::zero(), self.size) {
-            return None;
-        }
-
-        self.grid[pos.y as usize][pos.x as usize].clone()
+    pub fn get(&self, pos: Vector) -> Option<&T> {
+        self.cells[self.index(pos)?].as_ref()
     }
 
     #[allow(dead_code)]
     pub fn get_mut(&mut self, pos: Vector) -> Option<&mut T> {
-        if !pos.contained_in(Vector::zero(), self.size) {
-            return None;
-        }
+        let index = self.index(pos)?;
+        self.cells[index].as_mut()
+    }
 
-        self.grid
-            .get_mut(pos.y as usize)
-            .unwrap()
-            .get_mut(pos.x as usize)
-            .unwrap()
-            .as_mut()
+    #[allow(dead_code)]
+    pub fn size(&self) -> Vector {
+        self.size
     }
 
     #[allow(dead_code)]
     pub fn contains(&self, pos: Vector) -> bool {
-        if !pos.contained_in(Vector::zero(), self.size) {
-            return false;
-        }
-
-        self.grid[pos.y as usize][pos.x as usize].is_some()
+        self.index(pos)
+            .is_some_and(|index| self.cells[index].is_some())
     }
 
     #[allow(dead_code)]
     pub fn iter(&self) -> VectorMapIterator<'_, T> {
         VectorMapIterator {
-            map: self,
-            index: 0,
+            cells: self.cells.iter().enumerate(),
+            size: self.size,
+        }
+    }
+
+    #[allow(dead_code)]
+    pub fn iter_mut(&mut self) -> VectorMapIterMut<'_, T> {
+        VectorMapIterMut {
+            cells: self.cells.iter_mut().enumerate(),
+            size: self.size,
         }
     }
 
     #[allow(dead_code)]
     pub fn len(&self) -> usize {
-        self.iter().count()
+        self.cells.iter().filter(|cell| cell.is_some()).count()
     }
 
     #[allow(dead_code)]
     pub fn is_empty(&self) -> bool {
-        self.iter().count() == 0
+        self.cells.iter().all(Option::is_none)
     }
 }
 
-pub struct VectorMapIterator<'a, T> {
-    map: &'a VectorMap<T>,
-    index: i64,
+impl<T: Clone> VectorMap<T> {
+    #[allow(dead_code)]
+    pub fn filled_with_value(size: Vector, value: T) -> Self {
+        Self {
+            cells: vec![Some(value); size.area() as usize],
+            size,
+        }
+    }
 }
 
-impl<'a, T: Clone> Iterator for VectorMapIterator<'a, T> {
-    type Item = (Vector, T);
+impl<T> VectorMap<T> {
+    /// Parses a rectangular grid with `T`'s [`TryFrom<char>`], failing on any other character.
+    ///
+    /// The positions of the `markers`, such as a start `S`, are returned alongside, while their
+    /// cells are still parsed like any other.
+    pub fn from_grid(grid: &[Rc<str>], markers: &[char]) -> ParseResult<(Self, Markers)>
+    where
+        T: TryFrom<char>,
+        T::Error: Display,
+    {
+        Self::parse_grid(grid, markers, |c| {
+            T::try_from(c)
+                .map(Some)
+                .map_err(|err| format!("invalid `{c}`: {err}"))
+        })
+    }
 
-    fn next(&mut self) -> Option<Self::Item> {
-        loop {
-            if self.index > self.map.size.area() {
-                return None;
+    /// Parses a rectangular grid with `cell`, leaving the cells it returns `None` for empty.
+    pub fn from_grid_with(
+        grid: &[Rc<str>],
+        markers: &[char],
+        cell: impl Fn(char) -> Option<T>,
+    ) -> ParseResult<(Self, Markers)> {
+        Self::parse_grid(grid, markers, |c| Ok(cell(c)))
+    }
+
+    fn parse_grid(
+        grid: &[Rc<str>],
+        markers: &[char],
+        cell: impl Fn(char) -> Result<Option<T>, String>,
+    ) -> ParseResult<(Self, Markers)> {
+        let width = grid.first().map_or(0, |line| line.chars().count());
+        if width == 0 {
+            return Err(ParseError::new("empty grid"));
+        }
+
+        let mut map = Self::new(Vector::new_usize(width, grid.len()));
+        let mut found = Markers::default();
+        for (y, line) in grid.iter().enumerate() {
+            let mut columns = 0;
+            for (x, c) in line.chars().enumerate() {
+                let pos = Vector::new_usize(x, y);
+                if markers.contains(&c) {
+                    found.0.entry(c).or_default().push(pos);
+                }
+                if let Some(value) = cell(c).map_err(|err| ParseError::at(y, x, err))? {
+                    map.insert(pos, value);
+                }
+                columns += 1;
             }
-            let pos = Vector::new(self.index % self.map.size.x, self.index / self.map.size.x);
-            self.index += 1;
-            if let Some(value) = self.map.get(pos) {
-                return Some((pos, value));
+            if columns != width {
+                let message =
+                    format!("expected {width} columns like the first line, found {columns}");
+                return Err(ParseError::on_line(y, message));
             }
         }
+        Ok((map, found))
+    }
+}
+
+/// The positions of the marker characters found by [`VectorMap::from_grid`], in reading order.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Markers(BTreeMap<char, Vec<Vector>>);
+
+impl Markers {
+    pub fn all(&self, marker: char) -> &[Vector] {
+        self.0.get(&marker).map_or(&[], Vec::as_slice)
+    }
+
+    /// The position of `marker`, which must appear exactly once.
+    pub fn one(&self, marker: char) -> ParseResult<Vector> {
+        match self.all(marker) {
+            [pos] => Ok(*pos),
+            [] => Err(ParseError::new(format!("missing `{marker}`"))),
+            [_, pos, ..] => Err(ParseError::at(
+                pos.y as usize,
+                pos.x as usize,
+                format!("more than one `{marker}`"),
+            )),
+        }
+    }
+}
+
+/// Panics if `pos` is off the grid or has no value, see [`VectorMap::get`].
+impl<T> Index<Vector> for VectorMap<T> {
+    type Output = T;
+
+    fn index(&self, pos: Vector) -> &T {
+        self.get(pos)
+            .unwrap_or_else(|| panic!("no value at {pos:?}"))
+    }
+}
+
+impl<T> IndexMut<Vector> for VectorMap<T> {
+    fn index_mut(&mut self, pos: Vector) -> &mut T {
+        self.get_mut(pos)
+            .unwrap_or_else(|| panic!("no value at {pos:?}"))
+    }
+}
+
+fn position(index: usize, size: Vector) -> Vector {
+    Vector::new(index as i64 % size.x, index as i64 / size.x)
+}
+
+pub struct VectorMapIterator<'a, T> {
+    cells: Enumerate<slice::Iter<'a, Option<T>>>,
+    size: Vector,
+}
+
+impl<'a, T> Iterator for VectorMapIterator<'a, T> {
+    type Item = (Vector, &'a T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let size = self.size;
+        (self.cells).find_map(|(index, cell)| Some((position(index, size), cell.as_ref()?)))
+    }
+}
+
+pub struct VectorMapIterMut<'a, T> {
+    cells: Enumerate<slice::IterMut<'a, Option<T>>>,
+    size: Vector,
+}
+
+impl<'a, T> Iterator for VectorMapIterMut<'a, T> {
+    type Item = (Vector, &'a mut T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let size = self.size;
+        (self.cells).find_map(|(index, cell)| Some((position(index, size), cell.as_mut()?)))
     }
 }
 
 pub struct VectorMapIntoIterator<T> {
-    map: VectorMap<T>,
-    index: i64,
+    cells: Enumerate<vec::IntoIter<Option<T>>>,
+    size: Vector,
 }
 
-impl<T: Clone> Iterator for VectorMapIntoIterator<T> {
+impl<T> Iterator for VectorMapIntoIterator<T> {
     type Item = (Vector, T);
 
     fn next(&mut self) -> Option<Self::Item> {
-        loop {
-            if self.index > self.map.size.area() {
-                return None;
-            }
-            let pos = Vector::new(self.index % self.map.size.x, self.index / self.map.size.x);
-            self.index += 1;
-            if let Some(value) = self.map.get(pos) {
-                return Some((pos, value));
-            }
-        }
+        let size = self.size;
+        (self.cells).find_map(|(index, cell)| Some((position(index, size), cell?)))
     }
 }
 
-impl<T: Clone> IntoIterator for VectorMap<T> {
+impl<T> IntoIterator for VectorMap<T> {
     type Item = (Vector, T);
     type IntoIter = VectorMapIntoIterator<T>;
 
     fn into_iter(self) -> Self::IntoIter {
         Self::IntoIter {
-            map: self,
-            index: 0,
+            cells: self.cells.into_iter().enumerate(),
+            size: self.size,
         }
     }
 }
 
+impl<'a, T> IntoIterator for &'a VectorMap<T> {
+    type Item = (Vector, &'a T);
+    type IntoIter = VectorMapIterator<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a mut VectorMap<T> {
+    type Item = (Vector, &'a mut T);
+    type IntoIter = VectorMapIterMut<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::solution::split;
+
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    enum Tile {
+        Wall,
+        Floor,
+    }
+
+    impl TryFrom<char> for Tile {
+        type Error = &'static str;
+
+        fn try_from(c: char) -> Result<Self, Self::Error> {
+            match c {
+                '#' => Ok(Tile::Wall),
+                '.' | 'S' | 'E' => Ok(Tile::Floor),
+                _ => Err("expected a tile"),
+            }
+        }
+    }
+
+    #[test]
+    fn from_grid() {
+        let raw = split("#S.\n.E#".into());
+        let (map, markers) = VectorMap::<Tile>::from_grid(&raw, &['S', 'E']).unwrap();
+        assert_eq!(map.size(), Vector::new(3, 2));
+        assert_eq!(map.get(Vector::new(0, 0)), Some(&Tile::Wall));
+        assert_eq!(map.get(Vector::new(1, 0)), Some(&Tile::Floor));
+        assert_eq!(markers.one('S'), Ok(Vector::new(1, 0)));
+        assert_eq!(markers.one('E'), Ok(Vector::new(1, 1)));
+        assert_eq!(markers.all('X'), []);
+    }
+
+    #[test]
+    fn from_grid_errors() {
+        let raw = split("#S.\n.x#".into());
+        let error = VectorMap::<Tile>::from_grid(&raw, &[]).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 2, column 2: invalid `x`: expected a tile"
+        );
+
+        let raw = split("#S.\n.E".into());
+        let error = VectorMap::<Tile>::from_grid(&raw, &[]).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 2: expected 3 columns like the first line, found 2"
+        );
+
+        let raw = split("S.S".into());
+        let (_, markers) = VectorMap::<Tile>::from_grid(&raw, &['S', 'E']).unwrap();
+        assert_eq!(markers.all('S'), [Vector::new(0, 0), Vector::new(2, 0)]);
+        let error = markers.one('S').unwrap_err();
+        assert_eq!(error.to_string(), "line 1, column 3: more than one `S`");
+        assert_eq!(markers.one('E').unwrap_err().to_string(), "missing `E`");
+    }
+
+    #[test]
+    fn from_grid_with() {
+        let raw = split("#.\n.#".into());
+        let (map, _) = VectorMap::from_grid_with(&raw, &[], |c| (c == '#').then_some(())).unwrap();
+        assert_eq!(map.len(), 2);
+        assert!(map.contains(Vector::new(1, 1)));
+        assert!(!map.contains(Vector::new(1, 0)));
+    }
+
+    #[test]
+    fn insert_remove() {
+        let mut map = VectorMap::new(Vector::new(3, 2));
+        assert_eq!(map.insert(Vector::new(2, 1), 'a'), Some(true));
+        assert_eq!(map.insert(Vector::new(2, 1), 'b'), Some(false));
+        assert_eq!(map.insert(Vector::new(3, 0), 'c'), None);
+        map.insert(Vector::new(1, 0), 'd');
+        *map.get_mut(Vector::new(1, 0)).unwrap() = 'e';
+        assert_eq!(map.len(), 2);
+        assert_eq!(map.remove(Vector::new(2, 1)), Some('b'));
+        assert_eq!(map.remove(Vector::new(2, 1)), None);
+        assert_eq!(
+            map.into_iter().collect::<Vec<_>>(),
+            [(Vector::new(1, 0), 'e')]
+        );
+    }
+
+    /// Not [`Clone`], so only usable through references.
+    #[derive(Debug, PartialEq)]
+    struct Cell(Vec<i64>);
+
+    #[test]
+    fn by_reference() {
+        let mut map = VectorMap::new(Vector::new(2, 2));
+        map.insert(Vector::new(0, 0), Cell(vec![1]));
+        map.insert(Vector::new(1, 1), Cell(vec![2, 3]));
+        assert_eq!(map.get(Vector::new(1, 1)), Some(&Cell(vec![2, 3])));
+        assert_eq!(map[Vector::new(0, 0)].0, [1]);
+
+        map[Vector::new(0, 0)].0.push(4);
+        for (pos, cell) in map.iter_mut() {
+            cell.0.push(pos.x);
+        }
+        let cells = map.iter().map(|(_, cell)| &cell.0[..]).collect::<Vec<_>>();
+        assert_eq!(cells, [&[1, 4, 0][..], &[2, 3, 1]]);
+    }
+
+    #[test]
+    #[should_panic(expected = "no value at")]
+    fn index_empty() {
+        let map: VectorMap<Cell> = VectorMap::new(Vector::new(2, 2));
+        let _ = &map[Vector::new(1, 0)];
+    }
 
     #[test]
     fn one_by_one_empty() {
@@ -180,7 +417,7 @@ mod test {
         let mut map: VectorMap<()> = VectorMap::new(Vector::new(1, 1));
         map.insert(Vector::new(0, 0), ());
         let mut iter = map.iter();
-        assert_eq!(iter.next(), Some((Vector::new(0, 0), ())));
+        assert_eq!(iter.next(), Some((Vector::new(0, 0), &())));
         assert_eq!(iter.next(), None);
     }
 
@@ -196,7 +433,7 @@ mod test {
         let mut map: VectorMap<()> = VectorMap::new(Vector::new(2, 2));
         map.insert(Vector::new(0, 0), ());
         let mut iter = map.iter();
-        assert_eq!(iter.next(), Some((Vector::new(0, 0), ())));
+        assert_eq!(iter.next(), Some((Vector::new(0, 0), &())));
         assert_eq!(iter.next(), None);
     }
 
@@ -205,7 +442,7 @@ mod test {
         let mut map = VectorMap::new(Vector::new(2, 2));
         map.insert(Vector::new(1, 0), ());
         let mut iter = map.iter();
-        assert_eq!(iter.next(), Some((Vector::new(1, 0), ())));
+        assert_eq!(iter.next(), Some((Vector::new(1, 0), &())));
         assert_eq!(iter.next(), None);
     }
 
@@ -214,7 +451,7 @@ mod test {
         let mut map = VectorMap::new(Vector::new(2, 2));
         map.insert(Vector::new(0, 1), ());
         let mut iter = map.iter();
-        assert_eq!(iter.next(), Some((Vector::new(0, 1), ())));
+        assert_eq!(iter.next(), Some((Vector::new(0, 1), &())));
         assert_eq!(iter.next(), None);
     }
 
@@ -223,7 +460,7 @@ mod test {
         let mut map = VectorMap::new(Vector::new(2, 2));
         map.insert(Vector::new(1, 1), ());
         let mut iter = map.iter();
-        assert_eq!(iter.next(), Some((Vector::new(1, 1), ())));
+        assert_eq!(iter.next(), Some((Vector::new(1, 1), &())));
         assert_eq!(iter.next(), None);
     }
 
@@ -233,8 +470,8 @@ mod test {
         map.insert(Vector::new(0, 0), ());
         map.insert(Vector::new(1, 0), ());
         let mut iter = map.iter();
-        assert_eq!(iter.next(), Some((Vector::new(0, 0), ())));
-        assert_eq!(iter.next(), Some((Vector::new(1, 0), ())));
+        assert_eq!(iter.next(), Some((Vector::new(0, 0), &())));
+        assert_eq!(iter.next(), Some((Vector::new(1, 0), &())));
         assert_eq!(iter.next(), None);
     }
 
@@ -244,8 +481,8 @@ mod test {
         map.insert(Vector::new(0, 0), ());
         map.insert(Vector::new(0, 1), ());
         let mut iter = map.iter();
-        assert_eq!(iter.next(), Some((Vector::new(0, 0), ())));
-        assert_eq!(iter.next(), Some((Vector::new(0, 1), ())));
+        assert_eq!(iter.next(), Some((Vector::new(0, 0), &())));
+        assert_eq!(iter.next(), Some((Vector::new(0, 1), &())));
         assert_eq!(iter.next(), None);
     }
 
@@ -255,8 +492,8 @@ mod test {
         map.insert(Vector::new(0, 0), ());
         map.insert(Vector::new(1, 1), ());
         let mut iter = map.iter();
-        assert_eq!(iter.next(), Some((Vector::new(0, 0), ())));
-        assert_eq!(iter.next(), Some((Vector::new(1, 1), ())));
+        assert_eq!(iter.next(), Some((Vector::new(0, 0), &())));
+        assert_eq!(iter.next(), Some((Vector::new(1, 1), &())));
         assert_eq!(iter.next(), None);
     }
 
@@ -266,8 +503,8 @@ mod test {
         map.insert(Vector::new(1, 0), ());
         map.insert(Vector::new(0, 1), ());
         let mut iter = map.iter();
-        assert_eq!(iter.next(), Some((Vector::new(1, 0), ())));
-        assert_eq!(iter.next(), Some((Vector::new(0, 1), ())));
+        assert_eq!(iter.next(), Some((Vector::new(1, 0), &())));
+        assert_eq!(iter.next(), Some((Vector::new(0, 1), &())));
         assert_eq!(iter.next(), None);
     }
 
@@ -277,8 +514,8 @@ mod test {
         map.insert(Vector::new(1, 0), ());
         map.insert(Vector::new(1, 1), ());
         let mut iter = map.iter();
-        assert_eq!(iter.next(), Some((Vector::new(1, 0), ())));
-        assert_eq!(iter.next(), Some((Vector::new(1, 1), ())));
+        assert_eq!(iter.next(), Some((Vector::new(1, 0), &())));
+        assert_eq!(iter.next(), Some((Vector::new(1, 1), &())));
         assert_eq!(iter.next(), None);
     }
 
@@ -288,8 +525,8 @@ mod test {
         map.insert(Vector::new(0, 1), ());
         map.insert(Vector::new(1, 1), ());
         let mut iter = map.iter();
-        assert_eq!(iter.next(), Some((Vector::new(0, 1), ())));
-        assert_eq!(iter.next(), Some((Vector::new(1, 1), ())));
+        assert_eq!(iter.next(), Some((Vector::new(0, 1), &())));
+        assert_eq!(iter.next(), Some((Vector::new(1, 1), &())));
         assert_eq!(iter.next(), None);
     }
 
@@ -301,10 +538,10 @@ mod test {
         map.insert(Vector::new(1, 0), ());
         map.insert(Vector::new(1, 1), ());
         let mut iter = map.iter();
-        assert_eq!(iter.next(), Some((Vector::new(0, 0), ())));
-        assert_eq!(iter.next(), Some((Vector::new(1, 0), ())));
-        assert_eq!(iter.next(), Some((Vector::new(0, 1), ())));
-        assert_eq!(iter.next(), Some((Vector::new(1, 1), ())));
+        assert_eq!(iter.next(), Some((Vector::new(0, 0), &())));
+        assert_eq!(iter.next(), Some((Vector::new(1, 0), &())));
+        assert_eq!(iter.next(), Some((Vector::new(0, 1), &())));
+        assert_eq!(iter.next(), Some((Vector::new(1, 1), &())));
         assert_eq!(iter.next(), None);
     }
 }
@@ -2,9 +2,10 @@ use std::{collections::BTreeSet, rc::Rc};
 
 use super::Vector;
 
+/// Positions on a grid of `size`, stored row by row as one bit each.
 #[derive(Debug, Clone)]
 pub struct VectorSet {
-    grid: Vec<Vec<bool>>,
+    bits: Vec<u64>,
     size: Vector,
     count: usize,
 }
@@ -13,12 +14,24 @@ impl VectorSet {
     #[allow(dead_code)]
     pub fn new(size: Vector) -> Self {
         Self {
-            grid: vec![vec![false; size.x as usize]; size.y as usize],
+            bits: vec![0; (size.area() as usize).div_ceil(64)],
             size,
             count: 0,
         }
     }
 
+    /// The word and bit of `pos`, if it's on the grid.
+    fn index(&self, pos: Vector) -> Option<(usize, u64)> {
+        // Negative coordinates wrap around to beyond the size.
+        let (x, y, width) = (pos.x as usize, pos.y as usize, self.size.x as usize);
+        if x >= width || y >= self.size.y as usize {
+            return None;
+        }
+
+        let index = y * width + x;
+        Some((index / 64, 1 << (index % 64)))
+    }
+
     #[allow(dead_code)]
     pub fn from_grid(grid: &[Rc<str>], ch: char) -> VectorSet {
         let size = Vector::new_usize(grid[0].len(), grid.len());
@@ -37,12 +50,9 @@ impl VectorSet {
 
     #[allow(dead_code)]
     pub fn insert(&mut self, pos: Vector) -> Option<bool> {
-        if !pos.contained_in(Vector::zero(), self.size) {
-            return None;
-        }
-
-        let old = self.grid[pos.y as usize][pos.x as usize];
-        self.grid[pos.y as usize][pos.x as usize] = true;
+        let (word, bit) = self.index(pos)?;
+        let old = self.bits[word] & bit != 0;
+        self.bits[word] |= bit;
         if !old {
             self.count += 1;
         }
@@ -51,12 +61,9 @@ impl VectorSet {
 
     #[allow(dead_code)]
     pub fn remove(&mut self, pos: Vector) -> Option<bool> {
-        if !pos.contained_in(Vector::zero(), self.size) {
-            return None;
-        }
-
-        let old = self.grid[pos.y as usize][pos.x as usize];
-        self.grid[pos.y as usize][pos.x as usize] = false;
+        let (word, bit) = self.index(pos)?;
+        let old = self.bits[word] & bit != 0;
+        self.bits[word] &= !bit;
         if old {
             self.count -= 1;
         }
@@ -64,19 +71,21 @@ impl VectorSet {
     }
 
     #[allow(dead_code)]
-    pub fn contains(&self, pos: Vector) -> bool {
-        if !pos.contained_in(Vector::zero(), self.size) {
-            return false;
-        }
+    pub fn size(&self) -> Vector {
+        self.size
+    }
 
-        self.grid[pos.y as usize][pos.x as usize]
+    #[allow(dead_code)]
+    pub fn contains(&self, pos: Vector) -> bool {
+        self.index(pos)
+            .is_some_and(|(word, bit)| self.bits[word] & bit != 0)
     }
 
     #[allow(dead_code)]
     pub fn iter(&self) -> VectorSetIterator<'_> {
         VectorSetIterator {
-            set: self,
-            index: 0,
+            bits: Bits::new(self.bits.iter().copied()),
+            size: self.size,
         }
     }
 
@@ -139,47 +148,64 @@ impl VectorSet {
     }
 }
 
+/// The indices of the set bits, skipping empty words.
+struct Bits<W> {
+    words: W,
+    offset: usize,
+    word: u64,
+}
+
+impl<W: Iterator<Item = u64>> Bits<W> {
+    fn new(words: impl IntoIterator<IntoIter = W>) -> Self {
+        Self {
+            words: words.into_iter(),
+            offset: 0,
+            word: 0,
+        }
+    }
+}
+
+impl<W: Iterator<Item = u64>> Iterator for Bits<W> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.word == 0 {
+            self.word = self.words.next()?;
+            self.offset += 64;
+        }
+        let bit = self.word.trailing_zeros() as usize;
+        self.word &= self.word - 1;
+        Some(self.offset - 64 + bit)
+    }
+}
+
+fn position(index: usize, size: Vector) -> Vector {
+    Vector::new(index as i64 % size.x, index as i64 / size.x)
+}
+
 pub struct VectorSetIterator<'a> {
-    set: &'a VectorSet,
-    index: i64,
+    bits: Bits<std::iter::Copied<std::slice::Iter<'a, u64>>>,
+    size: Vector,
 }
 
 impl<'a> Iterator for VectorSetIterator<'a> {
     type Item = Vector;
 
     fn next(&mut self) -> Option<Self::Item> {
-        loop {
-            if self.index > self.set.size.area() {
-                return None;
-            }
-            let pos = Vector::new(self.index % self.set.size.x, self.index / self.set.size.x);
-            self.index += 1;
-            if self.set.contains(pos) {
-                return Some(pos);
-            }
-        }
+        self.bits.next().map(|index| position(index, self.size))
     }
 }
 
 pub struct VectorSetIntoIterator {
-    set: VectorSet,
-    index: i64,
+    bits: Bits<std::vec::IntoIter<u64>>,
+    size: Vector,
 }
 
 impl Iterator for VectorSetIntoIterator {
     type Item = Vector;
 
     fn next(&mut self) -> Option<Self::Item> {
-        loop {
-            if self.index > self.set.size.area() {
-                return None;
-            }
-            let pos = Vector::new(self.index % self.set.size.x, self.index / self.set.size.x);
-            self.index += 1;
-            if self.set.contains(pos) {
-                return Some(pos);
-            }
-        }
+        self.bits.next().map(|index| position(index, self.size))
     }
 }
 
@@ -189,8 +215,8 @@ impl IntoIterator for VectorSet {
 
     fn into_iter(self) -> Self::IntoIter {
         Self::IntoIter {
-            set: self,
-            index: 0,
+            bits: Bits::new(self.bits),
+            size: self.size,
         }
     }
 }
@@ -358,6 +384,24 @@ mod test {
         assert_eq!(iter.next(), None);
     }
 
+    #[test]
+    fn across_words() {
+        let mut set = VectorSet::new(Vector::new(70, 3));
+        let positions = [Vector::new(63, 0), Vector::new(64, 0), Vector::new(69, 2)];
+        for pos in positions {
+            assert_eq!(set.insert(pos), Some(true));
+        }
+        assert_eq!(set.insert(Vector::new(64, 0)), Some(false));
+        assert_eq!(set.insert(Vector::new(70, 0)), None);
+        assert_eq!(set.len(), 3);
+        assert_eq!(set.iter().collect::<Vec<_>>(), positions);
+
+        assert_eq!(set.remove(Vector::new(63, 0)), Some(true));
+        assert!(!set.contains(Vector::new(63, 0)));
+        assert!(set.contains(Vector::new(64, 0)));
+        assert_eq!(set.into_iter().collect::<Vec<_>>(), positions[1..]);
+    }
+
     #[test]
     fn orientations_all_8() {
         let mut set = VectorSet::new(Vector::new(3, 3));